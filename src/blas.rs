@@ -0,0 +1,314 @@
+use std::any::Any;
+use std::cell::Cell;
+use std::os::raw::{c_char, c_int, c_void};
+
+use crate::matrix::Mat;
+use crate::vector::Vector;
+use crate::decompositions::lup::LUP;
+use crate::decompositions::qr::QR;
+use crate::decompositions::eig::EIG;
+
+
+// Routes matmul, vecmul, lup, qr, eig and eigh of contiguous f64 matrices to the
+// system BLAS/LAPACK. Fortran routines take every argument by reference and
+// store matrices in column-major order.
+#[link(name = "openblas")]
+extern "C"
+{
+    fn dgemm_(transa: *const c_char, transb: *const c_char, m: *const c_int, n: *const c_int, k: *const c_int,
+              alpha: *const f64, a: *const f64, lda: *const c_int, b: *const f64, ldb: *const c_int,
+              beta: *const f64, c: *mut f64, ldc: *const c_int);
+    fn dgemv_(trans: *const c_char, m: *const c_int, n: *const c_int, alpha: *const f64, a: *const f64,
+              lda: *const c_int, x: *const f64, incx: *const c_int, beta: *const f64, y: *mut f64,
+              incy: *const c_int);
+    fn dgetrf_(m: *const c_int, n: *const c_int, a: *mut f64, lda: *const c_int, ipiv: *mut c_int,
+               info: *mut c_int);
+    fn dgeqrf_(m: *const c_int, n: *const c_int, a: *mut f64, lda: *const c_int, tau: *mut f64,
+               work: *mut f64, lwork: *const c_int, info: *mut c_int);
+    fn dsyev_(jobz: *const c_char, uplo: *const c_char, n: *const c_int, a: *mut f64, lda: *const c_int,
+              w: *mut f64, work: *mut f64, lwork: *const c_int, info: *mut c_int);
+    fn dgees_(jobvs: *const c_char, sort: *const c_char, select: *const c_void, n: *const c_int, a: *mut f64,
+              lda: *const c_int, sdim: *mut c_int, wr: *mut f64, wi: *mut f64, vs: *mut f64, ldvs: *const c_int,
+              work: *mut f64, lwork: *const c_int, bwork: *mut c_int, info: *mut c_int);
+}
+
+thread_local!
+{
+    static ENABLED: Cell<bool> = const { Cell::new(true) };
+}
+
+// Puts the previous flag back when dropped, also while unwinding.
+struct Restore(bool);
+
+impl Drop for Restore
+{
+    fn drop(&mut self)
+    {
+        ENABLED.with(|e| e.set(self.0));
+    }
+}
+
+// Runs f with the pure Rust kernels on the current thread.
+pub fn native<R>(f: impl FnOnce() -> R) -> R
+{
+    let _restore = Restore(ENABLED.with(|e| e.replace(false)));
+    f()
+}
+
+fn _enabled() -> bool
+{
+    ENABLED.with(|e| e.get())
+}
+
+fn _as_f64<T: 'static>(mat: &Mat<T>) -> Option<&Mat<f64>>
+{
+    (mat as &dyn Any).downcast_ref::<Mat<f64>>()
+}
+
+fn _from_f64<T: 'static>(mat: Mat<f64>) -> Option<Mat<T>>
+{
+    let boxed: Box<dyn Any> = Box::new(mat);
+    boxed.downcast::<Mat<T>>().ok().map(|b| *b)
+}
+
+// Transpose flag and leading dimension of a contiguous matrix seen as a
+// column-major array holding its transpose.
+fn _transposed_layout(mat: &Mat<f64>) -> Option<(c_char, c_int)>
+{
+    if mat.rows == 0 || mat.cols == 0
+    {
+        return None;
+    }
+    if mat._is_row_major()
+    {
+        Some((b'N' as c_char, mat.cols as c_int))
+    }
+    else if mat._is_col_major()
+    {
+        Some((b'T' as c_char, mat.rows as c_int))
+    }
+    else
+    {
+        None
+    }
+}
+
+// Column-major copy of a contiguous matrix.
+fn _col_major(mat: &Mat<f64>) -> Option<Vec<f64>>
+{
+    if mat.rows == 0 || mat.cols == 0
+    {
+        return None;
+    }
+    if mat._is_col_major()
+    {
+        return Some(mat._elements().to_vec());
+    }
+    if !mat._is_row_major()
+    {
+        return None;
+    }
+    let mut data = Vec::with_capacity(mat.rows * mat.cols);
+    for c in 0..mat.cols
+    {
+        for r in 0..mat.rows
+        {
+            data.push(mat[(r, c)]);
+        }
+    }
+    Some(data)
+}
+
+fn _from_col_major(data: &[f64], rows: usize, cols: usize) -> Mat<f64>
+{
+    let mut mat = Mat::new((rows, cols));
+    for c in 0..cols
+    {
+        for r in 0..rows
+        {
+            mat[(r, c)] = data[c * rows + r];
+        }
+    }
+    mat
+}
+
+pub(crate) fn matmul<T: 'static>(a: &Mat<T>, b: &Mat<T>) -> Option<Mat<T>>
+{
+    if !_enabled()
+    {
+        return None;
+    }
+    let (a, b) = (_as_f64(a)?, _as_f64(b)?);
+    let (ta, lda) = _transposed_layout(a)?;
+    let (tb, ldb) = _transposed_layout(b)?;
+
+    // The row-major output is cᵀ = bᵀ aᵀ in column-major order.
+    let (m, n, k) = (b.cols as c_int, a.rows as c_int, a.cols as c_int);
+    let mut out = vec![0.0; a.rows * b.cols];
+    unsafe
+    {
+        dgemm_(&tb, &ta, &m, &n, &k, &1.0, b._elements().as_ptr(), &ldb, a._elements().as_ptr(), &lda,
+               &0.0, out.as_mut_ptr(), &m);
+    }
+    _from_f64(Mat::from_vec((a.rows, b.cols), out))
+}
+
+pub(crate) fn vecmul<T: 'static>(a: &Mat<T>, x: &Vector<T>) -> Option<Vector<T>>
+{
+    if !_enabled()
+    {
+        return None;
+    }
+    let a = _as_f64(a)?;
+    let x = (x as &dyn Any).downcast_ref::<Vector<f64>>()?;
+    let (ta, lda) = _transposed_layout(a)?;
+
+    // aᵀ is stored, so the stored array is transposed back unless a is
+    // column-major.
+    let trans = if ta == b'N' as c_char { b'T' as c_char } else { b'N' as c_char };
+    let (m, n) = if a._is_row_major() { (a.cols, a.rows) } else { (a.rows, a.cols) };
+    let x = x.iter().copied().collect::<Vec<f64>>();
+    let mut y = vec![0.0; a.rows];
+    unsafe
+    {
+        dgemv_(&trans, &(m as c_int), &(n as c_int), &1.0, a._elements().as_ptr(), &lda, x.as_ptr(), &1,
+               &0.0, y.as_mut_ptr(), &1);
+    }
+    let boxed: Box<dyn Any> = Box::new(Vector::from_vec(y));
+    boxed.downcast::<Vector<T>>().ok().map(|b| *b)
+}
+
+pub(crate) fn lup(a: &Mat<f64>) -> Option<LUP<f64>>
+{
+    if !_enabled()
+    {
+        return None;
+    }
+    let n = a.rows;
+    let mut data = _col_major(a)?;
+    let mut ipiv = vec![0 as c_int; n];
+    let mut info = 0;
+    unsafe
+    {
+        dgetrf_(&(n as c_int), &(n as c_int), data.as_mut_ptr(), &(n as c_int), ipiv.as_mut_ptr(), &mut info);
+    }
+    // info > 0 flags an exactly zero pivot, the factorization is still complete.
+    if info < 0
+    {
+        return None;
+    }
+
+    let mut perm = (0..n).collect::<Vec<usize>>();
+    let mut num_swaps = 0;
+    for (i, &p) in ipiv.iter().enumerate()
+    {
+        let p = p as usize - 1;
+        if p != i
+        {
+            perm.swap(i, p);
+            num_swaps += 1;
+        }
+    }
+    Some(LUP { compact: _from_col_major(&data, n, n), perm, num_swaps })
+}
+
+pub(crate) fn qr(a: &Mat<f64>) -> Option<QR<f64>>
+{
+    if !_enabled()
+    {
+        return None;
+    }
+    let (m, n) = a.shape();
+    let mut data = _col_major(a)?;
+    let mut tau = vec![0.0; std::cmp::min(m, n)];
+    let (mc, nc) = (m as c_int, n as c_int);
+    let mut info = 0;
+
+    let mut query = 0.0;
+    unsafe
+    {
+        dgeqrf_(&mc, &nc, data.as_mut_ptr(), &mc, tau.as_mut_ptr(), &mut query, &-1, &mut info);
+    }
+    let lwork = std::cmp::max(query as usize, 1);
+    let mut work = vec![0.0; lwork];
+    unsafe
+    {
+        dgeqrf_(&mc, &nc, data.as_mut_ptr(), &mc, tau.as_mut_ptr(), work.as_mut_ptr(), &(lwork as c_int), &mut info);
+    }
+    if info != 0
+    {
+        return None;
+    }
+    // LAPACK stores the reflectors exactly like QR does.
+    Some(QR { compact: _from_col_major(&data, m, n), tau, explicit_q: None })
+}
+
+pub(crate) fn eigh(a: &Mat<f64>) -> Option<EIG<f64>>
+{
+    if !_enabled()
+    {
+        return None;
+    }
+    let n = a.rows;
+    let mut data = _col_major(a)?;
+    let mut w = vec![0.0; n];
+    let nc = n as c_int;
+    let (jobz, uplo) = (b'V' as c_char, b'L' as c_char);
+    let mut info = 0;
+
+    let mut query = 0.0;
+    unsafe
+    {
+        dsyev_(&jobz, &uplo, &nc, data.as_mut_ptr(), &nc, w.as_mut_ptr(), &mut query, &-1, &mut info);
+    }
+    let lwork = std::cmp::max(query as usize, 1);
+    let mut work = vec![0.0; lwork];
+    unsafe
+    {
+        dsyev_(&jobz, &uplo, &nc, data.as_mut_ptr(), &nc, w.as_mut_ptr(), work.as_mut_ptr(), &(lwork as c_int), &mut info);
+    }
+    if info != 0
+    {
+        return None;
+    }
+    Some(EIG { eigvals: Vector::from_vec(w), eigvecs: _from_col_major(&data, n, n) })
+}
+
+// Real Schur form through dgees, with the same meaning as the iterative eig:
+// the diagonal of the Schur form and the Schur vectors.
+pub(crate) fn eig(a: &Mat<f64>) -> Option<EIG<f64>>
+{
+    if !_enabled()
+    {
+        return None;
+    }
+    let n = a.rows;
+    let mut data = _col_major(a)?;
+    let (mut wr, mut wi) = (vec![0.0; n], vec![0.0; n]);
+    let mut vs = vec![0.0; n * n];
+    let mut bwork = vec![0 as c_int; n];
+    let nc = n as c_int;
+    let (jobvs, sort) = (b'V' as c_char, b'N' as c_char);
+    let (mut sdim, mut info) = (0, 0);
+
+    let mut query = 0.0;
+    unsafe
+    {
+        dgees_(&jobvs, &sort, std::ptr::null(), &nc, data.as_mut_ptr(), &nc, &mut sdim, wr.as_mut_ptr(),
+               wi.as_mut_ptr(), vs.as_mut_ptr(), &nc, &mut query, &-1, bwork.as_mut_ptr(), &mut info);
+    }
+    let lwork = std::cmp::max(query as usize, 3 * n);
+    let mut work = vec![0.0; lwork];
+    unsafe
+    {
+        dgees_(&jobvs, &sort, std::ptr::null(), &nc, data.as_mut_ptr(), &nc, &mut sdim, wr.as_mut_ptr(),
+               wi.as_mut_ptr(), vs.as_mut_ptr(), &nc, work.as_mut_ptr(), &(lwork as c_int), bwork.as_mut_ptr(),
+               &mut info);
+    }
+    if info != 0
+    {
+        return None;
+    }
+    // Standardized 2x2 blocks have the real part of the pair on the diagonal.
+    Some(EIG { eigvals: Vector::from_vec(wr), eigvecs: _from_col_major(&vs, n, n) })
+}
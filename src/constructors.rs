@@ -0,0 +1,148 @@
+use crate::matrix::Mat;
+use crate::vector::Vector;
+
+
+impl<T> Mat<T>
+where T: Default + Clone
+{
+    pub fn zeros(shape: (usize, usize)) -> Self
+    {
+        Mat::new(shape)
+    }
+
+    pub fn full(shape: (usize, usize), value: T) -> Self
+    {
+        Mat::from_vec(shape, vec![value; shape.0 * shape.1])
+    }
+
+    pub fn from_fn(shape: (usize, usize), f: impl Fn(usize, usize) -> T) -> Self
+    {
+        let mut elements = Vec::with_capacity(shape.0 * shape.1);
+        for r in 0..shape.0
+        {
+            for c in 0..shape.1
+            {
+                elements.push(f(r, c));
+            }
+        }
+        Mat::from_vec(shape, elements)
+    }
+
+    pub fn from_diag(diag: &Vector<T>) -> Self
+    {
+        let mut mat = Mat::new((diag.len(), diag.len()));
+        for (i, x) in diag.iter().enumerate()
+        {
+            mat[(i, i)] = x.clone();
+        }
+        mat
+    }
+
+    pub fn from_rows(rows: &[Vector<T>]) -> Self
+    {
+        let cols = rows.first().map_or(0, |r| r.len());
+        assert!(rows.iter().all(|r| r.len() == cols), "Rows must have equal lengths.");
+        Mat::from_fn((rows.len(), cols), |r, c| rows[r][c].clone())
+    }
+
+    pub fn from_cols(cols: &[Vector<T>]) -> Self
+    {
+        let rows = cols.first().map_or(0, |c| c.len());
+        assert!(cols.iter().all(|c| c.len() == rows), "Columns must have equal lengths.");
+        Mat::from_fn((rows, cols.len()), |r, c| cols[c][r].clone())
+    }
+
+    pub fn hstack(mats: &[&Mat<T>]) -> Self
+    {
+        let rows = mats.first().map_or(0, |m| m.rows);
+        assert!(mats.iter().all(|m| m.rows == rows), "Matrices must have equal row counts.");
+        let mut out = Mat::new((rows, mats.iter().map(|m| m.cols).sum()));
+        let mut offset = 0;
+        for mat in mats
+        {
+            out._copy_from(mat, 0, offset);
+            offset += mat.cols;
+        }
+        out
+    }
+
+    pub fn vstack(mats: &[&Mat<T>]) -> Self
+    {
+        let cols = mats.first().map_or(0, |m| m.cols);
+        assert!(mats.iter().all(|m| m.cols == cols), "Matrices must have equal column counts.");
+        let mut out = Mat::new((mats.iter().map(|m| m.rows).sum(), cols));
+        let mut offset = 0;
+        for mat in mats
+        {
+            out._copy_from(mat, offset, 0);
+            offset += mat.rows;
+        }
+        out
+    }
+
+    pub fn block_diag(mats: &[&Mat<T>]) -> Self
+    {
+        let shape = (mats.iter().map(|m| m.rows).sum(), mats.iter().map(|m| m.cols).sum());
+        let mut out = Mat::new(shape);
+        let (mut r, mut c) = (0, 0);
+        for mat in mats
+        {
+            out._copy_from(mat, r, c);
+            r += mat.rows;
+            c += mat.cols;
+        }
+        out
+    }
+
+    // Constant diagonals given by the first column and the first row,
+    // row[0] is ignored.
+    pub fn toeplitz(col: &Vector<T>, row: &Vector<T>) -> Self
+    {
+        Mat::from_fn((col.len(), row.len()), |r, c| if r >= c { col[r - c].clone() } else { row[c - r].clone() })
+    }
+
+    // Constant anti-diagonals given by the first column and the last row,
+    // row[0] is ignored.
+    pub fn hankel(col: &Vector<T>, row: &Vector<T>) -> Self
+    {
+        let m = col.len();
+        Mat::from_fn((m, row.len()), |r, c| if r + c < m { col[r + c].clone() } else { row[r + c + 1 - m].clone() })
+    }
+
+    // Every column is the previous one shifted down by one, starting at col.
+    pub fn circulant(col: &Vector<T>) -> Self
+    {
+        let n = col.len();
+        Mat::from_fn((n, n), |r, c| col[(n + r - c) % n].clone())
+    }
+
+    fn _copy_from(&mut self, mat: &Mat<T>, row: usize, col: usize)
+    {
+        for r in 0..mat.rows
+        {
+            for c in 0..mat.cols
+            {
+                self[(row + r, col + c)] = mat[(r, c)].clone();
+            }
+        }
+    }
+}
+
+impl Mat<f64>
+{
+    pub fn ones(shape: (usize, usize)) -> Mat<f64>
+    {
+        Mat::full(shape, 1.0)
+    }
+
+    // Increasing powers x[r]^c for c in 0..cols.
+    pub fn vandermonde(x: &Vector<f64>, cols: usize) -> Mat<f64>
+    {
+        Mat::from_fn((x.len(), cols), |r, c| x[r].powi(c as i32))
+    }
+
+    pub fn hilbert(size: usize) -> Mat<f64>
+    {
+        Mat::from_fn((size, size), |r, c| 1.0 / (r + c + 1) as f64)
+    }
+}
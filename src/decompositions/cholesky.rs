@@ -0,0 +1,150 @@
+use crate::matrix::Mat;
+use crate::vector::Vector;
+
+
+// a = l.matmul(&l.transposed()) with l lower triangular.
+pub struct Cholesky<T>
+{
+    pub l: Mat<T>,
+}
+
+impl Mat<f64>
+{
+    pub fn cholesky(mut self) -> Result<Cholesky<f64>, &'static str>
+    {
+        assert!(self.rows == self.cols);
+        let n = self.rows;
+
+        for j in 0..n
+        {
+            let mut d = self[(j, j)];
+            for k in 0..j
+            {
+                d -= self[(j, k)] * self[(j, k)];
+            }
+            if d <= 0.0 || !d.is_finite()
+            {
+                return Err("Matrix is not positive definite.");
+            }
+            let d = d.sqrt();
+            self[(j, j)] = d;
+
+            for i in (j + 1)..n
+            {
+                let mut s = self[(i, j)];
+                for k in 0..j
+                {
+                    s -= self[(i, k)] * self[(j, k)];
+                }
+                self[(i, j)] = s / d;
+            }
+            for i in 0..j
+            {
+                self[(i, j)] = 0.0;
+            }
+        }
+        Ok(Cholesky { l: self })
+    }
+}
+
+impl Cholesky<f64>
+{
+    pub fn solve(&self, b: &Vector<f64>) -> Vector<f64>
+    {
+        let n = self.l.rows;
+        assert!(b.len() == n);
+        let mut x = b.clone();
+
+        for i in 0..n
+        {
+            for k in 0..i
+            {
+                x[i] -= self.l[(i, k)] * x[k];
+            }
+            x[i] /= self.l[(i, i)];
+        }
+
+        for i in (0..n).rev()
+        {
+            for k in (i + 1)..n
+            {
+                x[i] -= self.l[(k, i)] * x[k];
+            }
+            x[i] /= self.l[(i, i)];
+        }
+        x
+    }
+
+    pub fn inv(&self) -> Mat<f64>
+    {
+        let n = self.l.rows;
+        let mut inverse = Mat::new((n, n));
+        let mut e = Vector::new(n);
+        for j in 0..n
+        {
+            e[j] = 1.0;
+            let col = self.solve(&e);
+            e[j] = 0.0;
+            for i in 0..n
+            {
+                inverse[(i, j)] = col[i];
+            }
+        }
+        inverse
+    }
+
+    pub fn det(&self) -> f64
+    {
+        let d: f64 = self.l.iter_trace().product();
+        d * d
+    }
+
+    // Factorization of a + x xᵀ.
+    pub fn update(&mut self, x: &Vector<f64>)
+    {
+        let n = self.l.rows;
+        assert!(x.len() == n);
+        let mut x = x.clone();
+        for k in 0..n
+        {
+            let lkk = self.l[(k, k)];
+            let r = lkk.hypot(x[k]);
+            let (c, s) = (r / lkk, x[k] / lkk);
+            self.l[(k, k)] = r;
+            for i in (k + 1)..n
+            {
+                self.l[(i, k)] = (self.l[(i, k)] + s * x[i]) / c;
+                x[i] = c * x[i] - s * self.l[(i, k)];
+            }
+        }
+    }
+
+    // Factorization of a - x xᵀ, fails if that is not positive definite.
+    // The factorization is left unchanged on failure.
+    pub fn downdate(&mut self, x: &Vector<f64>) -> Result<(), &'static str>
+    {
+        let n = self.l.rows;
+        assert!(x.len() == n);
+        let mut x = x.clone();
+        let mut l = self.l.clone();
+        for k in 0..n
+        {
+            let lkk = l[(k, k)];
+            let d = (lkk - x[k]) * (lkk + x[k]);
+            if d <= 0.0 || !d.is_finite()
+            {
+                return Err("Downdated matrix is not positive definite.");
+            }
+            let r = d.sqrt();
+            let (c, s) = (r / lkk, x[k] / lkk);
+            l[(k, k)] = r;
+            for i in (k + 1)..n
+            {
+                l[(i, k)] = (l[(i, k)] - s * x[i]) / c;
+                x[i] = c * x[i] - s * l[(i, k)];
+            }
+        }
+        self.l = l;
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ use crate::matrix::Mat;
 use crate::vector::Vector;
 
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct EIG<T>
 {
     pub eigvals: Vector<T>,
@@ -11,9 +12,18 @@ pub struct EIG<T>
 
 impl Mat<f64>
 {
+    // Real Schur form by QR iteration. eigvals is the diagonal of the Schur
+    // form, so a complex pair only shows up as its real part, twice, and
+    // eigvecs holds the Schur vectors rather than eigenvectors. The blas path
+    // returns the same.
     pub fn eig(mut self, max_iter: usize) -> EIG<f64>
     {
         assert!(self.rows == self.cols);
+        #[cfg(feature = "blas")]
+        if let Some(eig) = crate::blas::eig(&self)
+        {
+            return eig;
+        }
 
         let n = self.rows;
         let mut eigvals = Vector::new(n);
@@ -28,13 +38,14 @@ impl Mat<f64>
             }
 
             let qr = self.qr();
-            self = qr.r.matmul(&qr.q);
+            let q = qr.q();
+            self = qr.r().matmul(&q);
             for j in 0..n
             {
                 self[(j, j)] += mu;
             }
 
-            eigvecs = eigvecs.matmul(&qr.q);
+            eigvecs = eigvecs.matmul(&q);
         }
 
         for i in 0..n
@@ -42,6 +53,93 @@ impl Mat<f64>
             eigvals[i] = self[(i, i)];
         }
 
+        EIG{eigvals, eigvecs}
+    }
+}
+
+impl Mat<f64>
+{
+    // Cyclic Jacobi for symmetric matrices, eigenvalues in ascending order.
+    pub fn eigh(mut self) -> EIG<f64>
+    {
+        assert!(self.rows == self.cols);
+        #[cfg(feature = "blas")]
+        if let Some(eig) = crate::blas::eigh(&self)
+        {
+            return eig;
+        }
+        let n = self.rows;
+        let mut v = Mat::eye(n);
+
+        for _ in 0..100
+        {
+            let mut off = 0.0;
+            let mut norm = 0.0;
+            for r in 0..n
+            {
+                for c in 0..n
+                {
+                    let sq = self[(r, c)] * self[(r, c)];
+                    norm += sq;
+                    if r != c
+                    {
+                        off += sq;
+                    }
+                }
+            }
+            if off <= f64::EPSILON * f64::EPSILON * norm
+            {
+                break;
+            }
+
+            for p in 0..n
+            {
+                for q in (p + 1)..n
+                {
+                    if self[(p, q)] == 0.0
+                    {
+                        continue;
+                    }
+                    let tau = (self[(q, q)] - self[(p, p)]) / (2.0 * self[(p, q)]);
+                    let sign = if tau >= 0.0 { 1.0 } else { -1.0 };
+                    let t = sign / (tau.abs() + (1.0 + tau * tau).sqrt());
+                    let c = 1.0 / (1.0 + t * t).sqrt();
+                    let s = t * c;
+
+                    for k in 0..n
+                    {
+                        let (akp, akq) = (self[(k, p)], self[(k, q)]);
+                        self[(k, p)] = c * akp - s * akq;
+                        self[(k, q)] = s * akp + c * akq;
+                    }
+                    for k in 0..n
+                    {
+                        let (apk, aqk) = (self[(p, k)], self[(q, k)]);
+                        self[(p, k)] = c * apk - s * aqk;
+                        self[(q, k)] = s * apk + c * aqk;
+                    }
+                    for k in 0..n
+                    {
+                        let (vkp, vkq) = (v[(k, p)], v[(k, q)]);
+                        v[(k, p)] = c * vkp - s * vkq;
+                        v[(k, q)] = s * vkp + c * vkq;
+                    }
+                }
+            }
+        }
+
+        let mut order = (0..n).collect::<Vec<usize>>();
+        order.sort_by(|&a, &b| self[(a, a)].total_cmp(&self[(b, b)]));
+        let mut eigvals = Vector::new(n);
+        let mut eigvecs = Mat::new((n, n));
+        for (j, &i) in order.iter().enumerate()
+        {
+            eigvals[j] = self[(i, i)];
+            for r in 0..n
+            {
+                eigvecs[(r, j)] = v[(r, i)];
+            }
+        }
         EIG{eigvals, eigvecs}
     }
 }
\ No newline at end of file
@@ -0,0 +1,698 @@
+use crate::matrix::Mat;
+use crate::vector::Vector;
+use crate::decompositions::eig::EIG;
+use crate::decompositions::householder::{_reflector, _reflector_last, _reflect_rows, _reflect_cols};
+use crate::decompositions::givens::Givens;
+
+
+// a = q.matmul(&s).matmul(&z.transposed()) and b = q.matmul(&t).matmul(&z.transposed()),
+// with s quasi upper triangular and t upper triangular.
+pub struct QZ<T>
+{
+    pub q: Mat<T>,
+    pub z: Mat<T>,
+    pub s: Mat<T>,
+    pub t: Mat<T>,
+    pub eigvals_re: Vector<T>,
+    pub eigvals_im: Vector<T>,
+}
+
+// Eigenpairs of a x = λ b x, infinite eigenvalues are f64::INFINITY. A complex
+// pair takes two consecutive entries, the first with positive imaginary part,
+// and its eigenvector is stored as the real part followed by the imaginary part.
+pub struct GeneralizedEIG<T>
+{
+    pub eigvals_re: Vector<T>,
+    pub eigvals_im: Vector<T>,
+    pub eigvecs: Mat<T>,
+}
+
+// Solves a x = λ b x for symmetric a and symmetric positive definite b.
+// Eigenvalues are ascending and the eigenvectors are b-orthonormal.
+pub fn generalized_eigh(a: &Mat<f64>, b: &Mat<f64>) -> Result<EIG<f64>, &'static str>
+{
+    assert!(a.rows == a.cols && a.shape() == b.shape());
+    let l = b.clone().cholesky()?.l;
+
+    // Reduce to the standard problem c y = λ y with c = l^-1 a l^-T.
+    let w = _solve_lower(&l, a);
+    let mut c = _solve_lower(&l, &w.transposed());
+    let n = a.rows;
+    for i in 0..n
+    {
+        for j in 0..i
+        {
+            let mean = 0.5 * (c[(i, j)] + c[(j, i)]);
+            c[(i, j)] = mean;
+            c[(j, i)] = mean;
+        }
+    }
+
+    let eig = c.eigh();
+    let eigvecs = _solve_lower_transposed(&l, &eig.eigvecs);
+    Ok(EIG { eigvals: eig.eigvals, eigvecs })
+}
+
+// Solves a x = λ b x for general a and b through the QZ decomposition, the
+// eigenvectors have unit norm.
+pub fn generalized_eig(a: &Mat<f64>, b: &Mat<f64>) -> Result<GeneralizedEIG<f64>, &'static str>
+{
+    let qz = a.clone().qz(b.clone())?;
+    let eigvecs = qz.eigvecs();
+    Ok(GeneralizedEIG { eigvals_re: qz.eigvals_re, eigvals_im: qz.eigvals_im, eigvecs })
+}
+
+impl Mat<f64>
+{
+    // Generalized real Schur form of the pencil (self, b). Zero pivots of t are
+    // deflated to the bottom of the active window, so b may be singular.
+    pub fn qz(self, b: Mat<f64>) -> Result<QZ<f64>, &'static str>
+    {
+        assert!(self.rows == self.cols && self.shape() == b.shape());
+        let n = self.rows;
+        let qr = b.qr();
+        let mut q = qr.q();
+        let mut t = qr.r();
+        let mut s = qr.apply_qt(&self);
+        let mut z = Mat::eye(n);
+
+        // Hessenberg-triangular reduction with Givens rotations.
+        for j in 0..n.saturating_sub(2)
+        {
+            for i in ((j + 2)..n).rev()
+            {
+                let g = Givens::new(s[(i - 1, j)], s[(i, j)]);
+                g.rotate_rows(&mut s, i - 1, i, j);
+                g.rotate_rows(&mut t, i - 1, i, i - 1);
+                g.rotate_cols(&mut q, i - 1, i, n);
+                s[(i, j)] = 0.0;
+
+                let g = Givens::new(t[(i, i)], -t[(i, i - 1)]);
+                g.rotate_cols(&mut t, i - 1, i, i + 1);
+                g.rotate_cols(&mut s, i - 1, i, n);
+                g.rotate_cols(&mut z, i - 1, i, n);
+                t[(i, i - 1)] = 0.0;
+            }
+        }
+
+        let mut anorm = 0.0;
+        for x in s.iter()
+        {
+            anorm += x * x;
+        }
+        let anorm = anorm.sqrt();
+        let bnorm = t.iter().map(|x| x * x).sum::<f64>().sqrt();
+
+        let mut hi = n.saturating_sub(1);
+        let mut iter = 0;
+        while hi > 0
+        {
+            let mut lo = hi;
+            while lo > 0
+            {
+                let mut scale = s[(lo - 1, lo - 1)].abs() + s[(lo, lo)].abs();
+                if scale == 0.0
+                {
+                    scale = anorm;
+                }
+                if s[(lo, lo - 1)].abs() <= f64::EPSILON * scale
+                {
+                    s[(lo, lo - 1)] = 0.0;
+                    break;
+                }
+                lo -= 1;
+            }
+
+            if lo == hi
+            {
+                hi -= 1;
+                iter = 0;
+            }
+            else if let Some(j) = (lo..=hi).rev().find(|&j| t[(j, j)].abs() <= f64::EPSILON * bnorm)
+            {
+                _deflate_infinite(&mut s, &mut t, &mut q, &mut z, lo, j, hi);
+            }
+            else if lo + 1 == hi
+            {
+                _split_block(&mut s, &mut t, &mut q, &mut z, lo);
+                if hi < 2
+                {
+                    break;
+                }
+                hi -= 2;
+                iter = 0;
+            }
+            else
+            {
+                iter += 1;
+                if iter >= 30 * n
+                {
+                    return Err("QZ iteration did not converge.");
+                }
+                _qz_step(&mut s, &mut t, &mut q, &mut z, lo, hi, iter);
+            }
+        }
+
+        let (eigvals_re, eigvals_im) = _eigvals(&s, &t);
+        Ok(QZ { q, z, s, t, eigvals_re, eigvals_im })
+    }
+}
+
+// Chases the zero pivot t[j, j] down to t[hi, hi] and splits it off together
+// with s[hi, hi], which leaves an infinite eigenvalue at hi.
+fn _deflate_infinite(s: &mut Mat<f64>, t: &mut Mat<f64>, q: &mut Mat<f64>, z: &mut Mat<f64>, lo: usize, j: usize, hi: usize)
+{
+    let n = s.rows;
+    t[(j, j)] = 0.0;
+    for k in j..hi
+    {
+        let g = Givens::new(t[(k, k + 1)], t[(k + 1, k + 1)]);
+        g.rotate_rows(t, k, k + 1, k + 1);
+        g.rotate_rows(s, k, k + 1, if k > lo { k - 1 } else { lo });
+        g.rotate_cols(q, k, k + 1, n);
+        t[(k + 1, k + 1)] = 0.0;
+
+        if k > lo
+        {
+            let g = Givens::new(s[(k + 1, k)], -s[(k + 1, k - 1)]);
+            g.rotate_cols(s, k - 1, k, k + 2);
+            g.rotate_cols(t, k - 1, k, k);
+            g.rotate_cols(z, k - 1, k, n);
+            s[(k + 1, k - 1)] = 0.0;
+        }
+    }
+
+    let g = Givens::new(s[(hi, hi)], -s[(hi, hi - 1)]);
+    g.rotate_cols(s, hi - 1, hi, hi + 1);
+    g.rotate_cols(t, hi - 1, hi, hi);
+    g.rotate_cols(z, hi - 1, hi, n);
+    s[(hi, hi - 1)] = 0.0;
+}
+
+// Eigenvalues of the pencil from its diagonal blocks.
+fn _eigvals(s: &Mat<f64>, t: &Mat<f64>) -> (Vector<f64>, Vector<f64>)
+{
+    let n = s.rows;
+    let mut eigvals_re = Vector::new(n);
+    let mut eigvals_im = Vector::new(n);
+    let mut i = 0;
+    while i < n
+    {
+        if i + 1 < n && s[(i + 1, i)] != 0.0
+        {
+            let (qa, qb, qc) = _block_poly(s, t, i);
+            let disc = qb * qb - 4.0 * qa * qc;
+            let re = -qb / (2.0 * qa);
+            let offset = disc.abs().sqrt() / (2.0 * qa.abs());
+            if disc < 0.0
+            {
+                eigvals_re[i] = re;
+                eigvals_re[i + 1] = re;
+                eigvals_im[i] = offset;
+                eigvals_im[i + 1] = -offset;
+            }
+            else
+            {
+                eigvals_re[i] = re + offset;
+                eigvals_re[i + 1] = re - offset;
+            }
+            i += 2;
+        }
+        else
+        {
+            eigvals_re[i] = if t[(i, i)] == 0.0 { f64::INFINITY } else { s[(i, i)] / t[(i, i)] };
+            i += 1;
+        }
+    }
+    (eigvals_re, eigvals_im)
+}
+
+impl QZ<f64>
+{
+    // Moves the eigenvalues for which select(re, im) holds to the leading
+    // block and returns its size.
+    pub fn reorder<F>(&mut self, select: F) -> usize
+    where F: Fn(f64, f64) -> bool
+    {
+        let n = self.s.rows;
+        let mut selected = 0;
+        let mut i = 0;
+        while i < n
+        {
+            let size = if i + 1 < n && self.s[(i + 1, i)] != 0.0 { 2 } else { 1 };
+            if select(self.eigvals_re[i], self.eigvals_im[i])
+            {
+                let mut pos = i;
+                while pos > selected
+                {
+                    let prev = if pos >= 2 && self.s[(pos - 1, pos - 2)] != 0.0 { 2 } else { 1 };
+                    _swap_blocks(self, pos - prev, prev, size);
+                    pos -= prev;
+                }
+                selected += size;
+            }
+            (self.eigvals_re, self.eigvals_im) = _eigvals(&self.s, &self.t);
+            i += size;
+        }
+        selected
+    }
+
+    // Right eigenvectors of the pencil, in the layout of GeneralizedEIG.
+    pub fn eigvecs(&self) -> Mat<f64>
+    {
+        let n = self.s.rows;
+        let mut blocks = Vec::new();
+        let mut i = 0;
+        while i < n
+        {
+            let size = if i + 1 < n && self.s[(i + 1, i)] != 0.0 { 2 } else { 1 };
+            blocks.push((i, size));
+            i += size;
+        }
+        let snorm = self.s.iter().map(|x| x * x).sum::<f64>().sqrt();
+        let tnorm = self.t.iter().map(|x| x * x).sum::<f64>().sqrt();
+
+        let mut eigvecs = Mat::new((n, n));
+        for (b, &(start, size)) in blocks.iter().enumerate()
+        {
+            // Eigenvalue alpha / beta with complex alpha, c = beta s - alpha t.
+            let (beta, alpha_re, alpha_im) = if size == 2
+            {
+                (1.0, self.eigvals_re[start], self.eigvals_im[start])
+            }
+            else
+            {
+                (self.t[(start, start)], self.s[(start, start)], 0.0)
+            };
+            let c = |r: usize, col: usize| (beta * self.s[(r, col)] - alpha_re * self.t[(r, col)], -alpha_im * self.t[(r, col)]);
+            let floor = (f64::EPSILON * (beta.abs() * snorm + alpha_re.hypot(alpha_im) * tnorm)).max(f64::MIN_POSITIVE);
+
+            // y = u + i v solves c y = 0 with a unit entry in the block.
+            let mut u = vec![0.0; n];
+            let mut v = vec![0.0; n];
+            if size == 1
+            {
+                u[start] = 1.0;
+            }
+            else
+            {
+                let row = |r: usize| (c(r, start), c(r, start + 1));
+                let ((a0, b0), (a1, b1)) = (row(start), row(start + 1));
+                let norm0 = a0.0.hypot(a0.1) + b0.0.hypot(b0.1);
+                let norm1 = a1.0.hypot(a1.1) + b1.0.hypot(b1.1);
+                let (first, second) = if norm0 >= norm1 { (a0, b0) } else { (a1, b1) };
+                (u[start], v[start]) = (-second.0, -second.1);
+                (u[start + 1], v[start + 1]) = first;
+            }
+
+            let end = start + size;
+            for &(k, m) in blocks[..b].iter().rev()
+            {
+                // Real form [[re, -im], [im, re]] of the complex block system.
+                let mut system = Mat::new((2 * m, 2 * m));
+                let mut rhs = Vector::new(2 * m);
+                for r in 0..m
+                {
+                    for l in 0..m
+                    {
+                        let (re, im) = c(k + r, k + l);
+                        system[(r, l)] = re;
+                        system[(r, m + l)] = -im;
+                        system[(m + r, l)] = im;
+                        system[(m + r, m + l)] = re;
+                    }
+                    for l in (k + m)..end
+                    {
+                        let (re, im) = c(k + r, l);
+                        rhs[r] -= re * u[l] - im * v[l];
+                        rhs[m + r] -= re * v[l] + im * u[l];
+                    }
+                }
+                let sol = _solve_perturbed(system, rhs, floor);
+                for r in 0..m
+                {
+                    u[k + r] = sol[r];
+                    v[k + r] = sol[m + r];
+                }
+            }
+
+            let mut x = vec![0.0; n];
+            let mut y = vec![0.0; n];
+            for r in 0..n
+            {
+                for l in 0..end
+                {
+                    x[r] += self.z[(r, l)] * u[l];
+                    y[r] += self.z[(r, l)] * v[l];
+                }
+            }
+            let norm = x.iter().chain(y.iter()).map(|e| e * e).sum::<f64>().sqrt();
+            for r in 0..n
+            {
+                eigvecs[(r, start)] = x[r] / norm;
+                if size == 2
+                {
+                    eigvecs[(r, start + 1)] = y[r] / norm;
+                }
+            }
+        }
+        eigvecs
+    }
+}
+
+// Gaussian elimination with partial pivoting where pivots below floor are
+// raised to it, so a repeated eigenvalue still gives a finite solution.
+fn _solve_perturbed(mut m: Mat<f64>, mut rhs: Vector<f64>, floor: f64) -> Vector<f64>
+{
+    let n = m.rows;
+    for k in 0..n
+    {
+        let p = (k..n).fold(k, |best, r| if m[(r, k)].abs() > m[(best, k)].abs() { r } else { best });
+        for col in 0..n
+        {
+            let tmp = m[(k, col)];
+            m[(k, col)] = m[(p, col)];
+            m[(p, col)] = tmp;
+        }
+        let tmp = rhs[k];
+        rhs[k] = rhs[p];
+        rhs[p] = tmp;
+        if m[(k, k)].abs() < floor
+        {
+            m[(k, k)] = if m[(k, k)] < 0.0 { -floor } else { floor };
+        }
+        for r in (k + 1)..n
+        {
+            let f = m[(r, k)] / m[(k, k)];
+            for col in k..n
+            {
+                m[(r, col)] -= f * m[(k, col)];
+            }
+            rhs[r] -= f * rhs[k];
+        }
+    }
+    for k in (0..n).rev()
+    {
+        for col in (k + 1)..n
+        {
+            rhs[k] -= m[(k, col)] * rhs[col];
+        }
+        rhs[k] /= m[(k, k)];
+    }
+    rhs
+}
+
+// Swaps the adjacent diagonal blocks of sizes p and q starting at k.
+fn _swap_blocks(qz: &mut QZ<f64>, k: usize, p: usize, q: usize)
+{
+    let n = qz.s.rows;
+    let m = p + q;
+
+    // s11 r - l s22 = -s12 and t11 r - l t22 = -t12, then [r; I] and [l; I]
+    // span the right and left deflating subspaces of the second block.
+    let pq = p * q;
+    let mut kron = Mat::new((2 * pq, 2 * pq));
+    let mut rhs = Vector::new(2 * pq);
+    for (offset, mat) in [(0, &qz.s), (pq, &qz.t)]
+    {
+        for c in 0..q
+        {
+            for r in 0..p
+            {
+                let row = offset + c * p + r;
+                for l in 0..p
+                {
+                    kron[(row, c * p + l)] += mat[(k + r, k + l)];
+                }
+                for l in 0..q
+                {
+                    kron[(row, pq + l * p + r)] -= mat[(k + p + l, k + p + c)];
+                }
+                rhs[row] = -mat[(k + r, k + p + c)];
+            }
+        }
+    }
+    let sol = kron.lup().solve(&rhs);
+
+    let mut right = Mat::new((m, q));
+    let mut left = Mat::new((m, q));
+    for c in 0..q
+    {
+        for r in 0..p
+        {
+            right[(r, c)] = sol[c * p + r];
+            left[(r, c)] = sol[pq + c * p + r];
+        }
+        right[(p + c, c)] = 1.0;
+        left[(p + c, c)] = 1.0;
+    }
+    let zs = right.qr().q();
+    let qs = left.qr().q();
+    _transform(qz, k, &qs, &zs);
+
+    // Zero the decoupled blocks and retriangularize the diagonal blocks of t.
+    for r in q..m
+    {
+        for c in 0..q
+        {
+            qz.s[(k + r, k + c)] = 0.0;
+            qz.t[(k + r, k + c)] = 0.0;
+        }
+    }
+    for (start, size) in [(k, q), (k + q, p)]
+    {
+        if size == 2
+        {
+            let g = Givens::new(qz.t[(start, start)], qz.t[(start + 1, start)]);
+            g.rotate_rows(&mut qz.s, start, start + 1, start);
+            g.rotate_rows(&mut qz.t, start, start + 1, start);
+            g.rotate_cols(&mut qz.q, start, start + 1, n);
+            qz.t[(start + 1, start)] = 0.0;
+        }
+    }
+}
+
+// s = qsᵀ s zs, t = qsᵀ t zs, q = q qs and z = z zs on the indices k..k+m.
+fn _transform(qz: &mut QZ<f64>, k: usize, qs: &Mat<f64>, zs: &Mat<f64>)
+{
+    let n = qz.s.rows;
+    let m = qs.rows;
+    let mut cache = vec![0.0; m];
+    for mat in [&mut qz.s, &mut qz.t]
+    {
+        for col in k..n
+        {
+            for (r, c) in cache.iter_mut().enumerate()
+            {
+                *c = mat[(k + r, col)];
+            }
+            for r in 0..m
+            {
+                mat[(k + r, col)] = (0..m).map(|l| qs[(l, r)] * cache[l]).sum();
+            }
+        }
+        for row in 0..std::cmp::min(n, k + m)
+        {
+            for (c, v) in cache.iter_mut().enumerate()
+            {
+                *v = mat[(row, k + c)];
+            }
+            for c in 0..m
+            {
+                mat[(row, k + c)] = (0..m).map(|l| cache[l] * zs[(l, c)]).sum();
+            }
+        }
+    }
+    for (mat, u) in [(&mut qz.q, qs), (&mut qz.z, zs)]
+    {
+        for row in 0..n
+        {
+            for (c, v) in cache.iter_mut().enumerate()
+            {
+                *v = mat[(row, k + c)];
+            }
+            for c in 0..m
+            {
+                mat[(row, k + c)] = (0..m).map(|l| cache[l] * u[(l, c)]).sum();
+            }
+        }
+    }
+}
+
+// One implicit double shift QZ sweep over the active window lo..=hi.
+fn _qz_step(s: &mut Mat<f64>, t: &mut Mat<f64>, q: &mut Mat<f64>, z: &mut Mat<f64>, lo: usize, hi: usize, iter: usize)
+{
+    let n = s.rows;
+
+    // Trace and determinant of the trailing 2x2 block of s t^-1.
+    let (trace, det) = if iter.is_multiple_of(10)
+    {
+        let w = (s[(hi, hi - 1)] / t[(hi - 1, hi - 1)]).abs() + (s[(hi - 1, hi - 2)] / t[(hi - 2, hi - 2)]).abs();
+        (1.5 * w, w * w)
+    }
+    else
+    {
+        let base = hi - 2;
+        let mut inv = [[0.0; 3]; 3];
+        for j in 0..3
+        {
+            inv[j][j] = 1.0 / t[(base + j, base + j)];
+            for r in (0..j).rev()
+            {
+                let mut sum = 0.0;
+                for k in (r + 1)..=j
+                {
+                    sum += t[(base + r, base + k)] * inv[k][j];
+                }
+                inv[r][j] = -sum / t[(base + r, base + r)];
+            }
+        }
+        let mut m = [[0.0; 2]; 2];
+        for r in 0..2
+        {
+            for c in 0..2
+            {
+                for k in 0..3
+                {
+                    m[r][c] += s[(base + 1 + r, base + k)] * inv[k][c + 1];
+                }
+            }
+        }
+        (m[0][0] + m[1][1], m[0][0] * m[1][1] - m[0][1] * m[1][0])
+    };
+
+    // First column of the double shift polynomial in s t^-1.
+    let l = lo;
+    let m11 = s[(l, l)] / t[(l, l)];
+    let m21 = s[(l + 1, l)] / t[(l, l)];
+    let m12 = (s[(l, l + 1)] - s[(l, l)] * t[(l, l + 1)] / t[(l, l)]) / t[(l + 1, l + 1)];
+    let m22 = (s[(l + 1, l + 1)] - s[(l + 1, l)] * t[(l, l + 1)] / t[(l, l)]) / t[(l + 1, l + 1)];
+    let m32 = s[(l + 2, l + 1)] / t[(l + 1, l + 1)];
+    let mut x = m11 * m11 + m12 * m21 - trace * m11 + det;
+    let mut y = m21 * (m11 + m22 - trace);
+    let mut w = m21 * m32;
+
+    for k in lo..(hi - 1)
+    {
+        let (v, tau) = _reflector(&[x, y, w]);
+        _reflect_rows(s, &v, tau, k, if k > lo { k - 1 } else { lo });
+        _reflect_rows(t, &v, tau, k, k);
+        _reflect_cols(q, &v, tau, k, n);
+        if k > lo
+        {
+            s[(k + 1, k - 1)] = 0.0;
+            s[(k + 2, k - 1)] = 0.0;
+        }
+
+        let rows = std::cmp::min(k + 4, hi + 1);
+        let (v, tau) = _reflector_last(&[t[(k + 2, k)], t[(k + 2, k + 1)], t[(k + 2, k + 2)]]);
+        _reflect_cols(s, &v, tau, k, rows);
+        _reflect_cols(t, &v, tau, k, k + 3);
+        _reflect_cols(z, &v, tau, k, n);
+        t[(k + 2, k)] = 0.0;
+        t[(k + 2, k + 1)] = 0.0;
+
+        let (v, tau) = _reflector_last(&[t[(k + 1, k)], t[(k + 1, k + 1)]]);
+        _reflect_cols(s, &v, tau, k, rows);
+        _reflect_cols(t, &v, tau, k, k + 2);
+        _reflect_cols(z, &v, tau, k, n);
+        t[(k + 1, k)] = 0.0;
+
+        x = s[(k + 1, k)];
+        y = s[(k + 2, k)];
+        if k + 3 <= hi
+        {
+            w = s[(k + 3, k)];
+        }
+    }
+
+    let (v, tau) = _reflector(&[x, y]);
+    _reflect_rows(s, &v, tau, hi - 1, hi - 2);
+    _reflect_rows(t, &v, tau, hi - 1, hi - 1);
+    _reflect_cols(q, &v, tau, hi - 1, n);
+    s[(hi, hi - 2)] = 0.0;
+
+    let (v, tau) = _reflector_last(&[t[(hi, hi - 1)], t[(hi, hi)]]);
+    _reflect_cols(s, &v, tau, hi - 1, hi + 1);
+    _reflect_cols(t, &v, tau, hi - 1, hi + 1);
+    _reflect_cols(z, &v, tau, hi - 1, n);
+    t[(hi, hi - 1)] = 0.0;
+}
+
+// Triangularizes the 2x2 block at k if its eigenvalues are real.
+fn _split_block(s: &mut Mat<f64>, t: &mut Mat<f64>, q: &mut Mat<f64>, z: &mut Mat<f64>, k: usize)
+{
+    let n = s.rows;
+    let (qa, qb, qc) = _block_poly(s, t, k);
+    let disc = qb * qb - 4.0 * qa * qc;
+    if disc < 0.0 || qa == 0.0
+    {
+        return;
+    }
+    let root = if qb >= 0.0 { -qb - disc.sqrt() } else { -qb + disc.sqrt() };
+    let lambda = if root == 0.0 { 0.0 } else { 2.0 * qc / root };
+
+    // Rotate the null vector of a - λ b into the first column.
+    let c11 = s[(k, k)] - lambda * t[(k, k)];
+    let c12 = s[(k, k + 1)] - lambda * t[(k, k + 1)];
+    let c21 = s[(k + 1, k)];
+    let c22 = s[(k + 1, k + 1)] - lambda * t[(k + 1, k + 1)];
+    let (u1, u2) = if c11.abs() + c12.abs() >= c21.abs() + c22.abs() { (-c12, c11) } else { (-c22, c21) };
+    let g = Givens::new(u1, u2);
+    g.rotate_cols(s, k, k + 1, k + 2);
+    g.rotate_cols(t, k, k + 1, k + 2);
+    g.rotate_cols(z, k, k + 1, n);
+
+    let g = Givens::new(t[(k, k)], t[(k + 1, k)]);
+    g.rotate_rows(s, k, k + 1, k);
+    g.rotate_rows(t, k, k + 1, k);
+    g.rotate_cols(q, k, k + 1, n);
+    s[(k + 1, k)] = 0.0;
+    t[(k + 1, k)] = 0.0;
+}
+
+// Coefficients of det(a - λ b) for the 2x2 block at k.
+fn _block_poly(s: &Mat<f64>, t: &Mat<f64>, k: usize) -> (f64, f64, f64)
+{
+    let (a11, a12, a21, a22) = (s[(k, k)], s[(k, k + 1)], s[(k + 1, k)], s[(k + 1, k + 1)]);
+    let (b11, b12, b22) = (t[(k, k)], t[(k, k + 1)], t[(k + 1, k + 1)]);
+    (b11 * b22, -(a11 * b22 + a22 * b11 - a21 * b12), a11 * a22 - a12 * a21)
+}
+
+// Solves l x = rhs for lower triangular l.
+fn _solve_lower(l: &Mat<f64>, rhs: &Mat<f64>) -> Mat<f64>
+{
+    let n = l.rows;
+    let mut x = rhs.clone();
+    for col in 0..rhs.cols
+    {
+        for i in 0..n
+        {
+            for k in 0..i
+            {
+                x[(i, col)] -= l[(i, k)] * x[(k, col)];
+            }
+            x[(i, col)] /= l[(i, i)];
+        }
+    }
+    x
+}
+
+// Solves lᵀ x = rhs for lower triangular l.
+fn _solve_lower_transposed(l: &Mat<f64>, rhs: &Mat<f64>) -> Mat<f64>
+{
+    let n = l.rows;
+    let mut x = rhs.clone();
+    for col in 0..rhs.cols
+    {
+        for i in (0..n).rev()
+        {
+            for k in (i + 1)..n
+            {
+                x[(i, col)] -= l[(k, i)] * x[(k, col)];
+            }
+            x[(i, col)] /= l[(i, i)];
+        }
+    }
+    x
+}
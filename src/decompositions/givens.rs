@@ -0,0 +1,54 @@
+use crate::matrix::Mat;
+use crate::vector::Vector;
+
+
+// Plane rotation [[c, s], [-s, c]] mapping (a, b) onto (hypot(a, b), 0).
+#[derive(Clone, Copy, Debug)]
+pub struct Givens
+{
+    pub c: f64,
+    pub s: f64,
+}
+
+impl Givens
+{
+    pub fn new(a: f64, b: f64) -> Givens
+    {
+        let r = a.hypot(b);
+        if r == 0.0
+        {
+            return Givens { c: 1.0, s: 0.0 };
+        }
+        Givens { c: a / r, s: b / r }
+    }
+
+    // Rotates rows p and r of m, from column start on.
+    pub fn rotate_rows(&self, m: &mut Mat<f64>, p: usize, r: usize, start: usize)
+    {
+        for col in start..m.cols
+        {
+            let (x, y) = (m[(p, col)], m[(r, col)]);
+            m[(p, col)] = self.c * x + self.s * y;
+            m[(r, col)] = -self.s * x + self.c * y;
+        }
+    }
+
+    // Rotates columns p and r of m, rows 0..end. Multiplies m by the
+    // transposed rotation from the right.
+    pub fn rotate_cols(&self, m: &mut Mat<f64>, p: usize, r: usize, end: usize)
+    {
+        for row in 0..end
+        {
+            let (x, y) = (m[(row, p)], m[(row, r)]);
+            m[(row, p)] = self.c * x + self.s * y;
+            m[(row, r)] = -self.s * x + self.c * y;
+        }
+    }
+
+    pub fn rotate_vec(&self, v: &mut Vector<f64>, p: usize, r: usize)
+    {
+        let (x, y) = (v[p], v[r]);
+        v[p] = self.c * x + self.s * y;
+        v[r] = -self.s * x + self.c * y;
+    }
+}
@@ -0,0 +1,59 @@
+use crate::matrix::Mat;
+
+
+// Householder reflector I - tau v vᵀ mapping x onto the first unit vector.
+pub(crate) fn _reflector(x: &[f64]) -> (Vec<f64>, f64)
+{
+    let norm = x.iter().map(|a| a * a).sum::<f64>().sqrt();
+    if norm == 0.0
+    {
+        return (vec![0.0; x.len()], 0.0);
+    }
+    let mut v = x.to_vec();
+    v[0] += if x[0] >= 0.0 { norm } else { -norm };
+    let vv = v.iter().map(|a| a * a).sum::<f64>();
+    (v, 2.0 / vv)
+}
+
+// Reflector mapping x onto the last unit vector.
+pub(crate) fn _reflector_last(x: &[f64]) -> (Vec<f64>, f64)
+{
+    let reversed = x.iter().rev().copied().collect::<Vec<f64>>();
+    let (mut v, tau) = _reflector(&reversed);
+    v.reverse();
+    (v, tau)
+}
+
+// Applies the reflector to rows k.. of m, from column start on.
+pub(crate) fn _reflect_rows(m: &mut Mat<f64>, v: &[f64], tau: f64, k: usize, start: usize)
+{
+    for col in start..m.cols
+    {
+        let mut dot = 0.0;
+        for (i, vi) in v.iter().enumerate()
+        {
+            dot += vi * m[(k + i, col)];
+        }
+        for (i, vi) in v.iter().enumerate()
+        {
+            m[(k + i, col)] -= tau * dot * vi;
+        }
+    }
+}
+
+// Applies the reflector to columns k.. of m, rows 0..end.
+pub(crate) fn _reflect_cols(m: &mut Mat<f64>, v: &[f64], tau: f64, k: usize, end: usize)
+{
+    for row in 0..end
+    {
+        let mut dot = 0.0;
+        for (i, vi) in v.iter().enumerate()
+        {
+            dot += vi * m[(row, k + i)];
+        }
+        for (i, vi) in v.iter().enumerate()
+        {
+            m[(row, k + i)] -= tau * dot * vi;
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use crate::matrix::Mat;
+use crate::vector::Vector;
+use crate::permutation::Permutation;
+
+
+// p·a·pᵀ = l·d·lᵀ with l unit lower triangular and d block diagonal with
+// 1x1 and 2x2 blocks.
+pub struct LDL<T>
+{
+    pub l: Mat<T>,
+    pub d: Mat<T>,
+    pub perm: Vec<usize>,
+}
+
+impl Mat<f64>
+{
+    // Bunch–Kaufman diagonal pivoting for symmetric indefinite matrices.
+    pub fn ldl(mut self) -> LDL<f64>
+    {
+        assert!(self.rows == self.cols);
+        let n = self.rows;
+        let alpha = (1.0 + 17f64.sqrt()) / 8.0;
+        let mut l = Mat::eye(n);
+        let mut d = Mat::new((n, n));
+        let mut perm = (0..n).collect::<Vec<usize>>();
+
+        let mut k = 0;
+        while k < n
+        {
+            let mut lambda = 0.0;
+            let mut r = k;
+            for i in (k + 1)..n
+            {
+                if self[(i, k)].abs() > lambda
+                {
+                    lambda = self[(i, k)].abs();
+                    r = i;
+                }
+            }
+
+            let mut size = 1;
+            if lambda > 0.0 && self[(k, k)].abs() < alpha * lambda
+            {
+                let mut sigma = 0.0f64;
+                for j in k..n
+                {
+                    if j != r
+                    {
+                        sigma = sigma.max(self[(r, j)].abs());
+                    }
+                }
+
+                if self[(k, k)].abs() * sigma >= alpha * lambda * lambda
+                {
+                    // Keep the 1x1 pivot at k.
+                }
+                else if self[(r, r)].abs() >= alpha * sigma
+                {
+                    _swap_symmetric(&mut self, &mut l, &mut perm, k, r);
+                }
+                else
+                {
+                    _swap_symmetric(&mut self, &mut l, &mut perm, k + 1, r);
+                    size = 2;
+                }
+            }
+
+            if size == 1
+            {
+                let pivot = self[(k, k)];
+                d[(k, k)] = pivot;
+                if pivot != 0.0
+                {
+                    for i in (k + 1)..n
+                    {
+                        l[(i, k)] = self[(i, k)] / pivot;
+                    }
+                    for i in (k + 1)..n
+                    {
+                        for j in (k + 1)..n
+                        {
+                            self[(i, j)] -= l[(i, k)] * self[(j, k)];
+                        }
+                    }
+                }
+            }
+            else
+            {
+                let (a, b, c) = (self[(k, k)], self[(k + 1, k)], self[(k + 1, k + 1)]);
+                let det = a * c - b * b;
+                d[(k, k)] = a;
+                d[(k + 1, k)] = b;
+                d[(k, k + 1)] = b;
+                d[(k + 1, k + 1)] = c;
+                for i in (k + 2)..n
+                {
+                    let (wk, wk1) = (self[(i, k)], self[(i, k + 1)]);
+                    l[(i, k)] = (c * wk - b * wk1) / det;
+                    l[(i, k + 1)] = (a * wk1 - b * wk) / det;
+                }
+                for i in (k + 2)..n
+                {
+                    for j in (k + 2)..n
+                    {
+                        self[(i, j)] -= l[(i, k)] * self[(j, k)] + l[(i, k + 1)] * self[(j, k + 1)];
+                    }
+                }
+            }
+            k += size;
+        }
+        LDL { l, d, perm }
+    }
+}
+
+// Swaps rows and columns i and j of the trailing matrix and rows of l.
+fn _swap_symmetric(a: &mut Mat<f64>, l: &mut Mat<f64>, perm: &mut [usize], i: usize, j: usize)
+{
+    if i == j
+    {
+        return;
+    }
+    a.swap_rows(i, j);
+    for row in 0..a.rows
+    {
+        let temp = a[(row, i)];
+        a[(row, i)] = a[(row, j)];
+        a[(row, j)] = temp;
+    }
+    for col in 0..std::cmp::min(i, j)
+    {
+        let temp = l[(i, col)];
+        l[(i, col)] = l[(j, col)];
+        l[(j, col)] = temp;
+    }
+    perm.swap(i, j);
+}
+
+impl LDL<f64>
+{
+    pub fn p(&self) -> Permutation
+    {
+        Permutation::from_vec(self.perm.clone())
+    }
+
+    pub fn solve(&self, b: &Vector<f64>) -> Vector<f64>
+    {
+        let n = self.perm.len();
+        assert!(b.len() == n);
+        let mut y = self.p().apply(b);
+
+        for i in 0..n
+        {
+            for k in 0..i
+            {
+                y[i] -= self.l[(i, k)] * y[k];
+            }
+        }
+
+        let mut k = 0;
+        while k < n
+        {
+            if k + 1 < n && self.d[(k + 1, k)] != 0.0
+            {
+                let (a, b, c) = (self.d[(k, k)], self.d[(k + 1, k)], self.d[(k + 1, k + 1)]);
+                let det = a * c - b * b;
+                let (y0, y1) = (y[k], y[k + 1]);
+                y[k] = (c * y0 - b * y1) / det;
+                y[k + 1] = (a * y1 - b * y0) / det;
+                k += 2;
+            }
+            else
+            {
+                y[k] /= self.d[(k, k)];
+                k += 1;
+            }
+        }
+
+        for i in (0..n).rev()
+        {
+            for k in (i + 1)..n
+            {
+                y[i] -= self.l[(k, i)] * y[k];
+            }
+        }
+
+        let mut x = Vector::new(n);
+        for (i, &p) in self.perm.iter().enumerate()
+        {
+            x[p] = y[i];
+        }
+        x
+    }
+
+    pub fn det(&self) -> f64
+    {
+        let n = self.perm.len();
+        let mut det = 1.0;
+        let mut k = 0;
+        while k < n
+        {
+            if k + 1 < n && self.d[(k + 1, k)] != 0.0
+            {
+                det *= self.d[(k, k)] * self.d[(k + 1, k + 1)] - self.d[(k + 1, k)] * self.d[(k, k + 1)];
+                k += 2;
+            }
+            else
+            {
+                det *= self.d[(k, k)];
+                k += 1;
+            }
+        }
+        det
+    }
+
+    // Numbers of positive, negative and zero eigenvalues of a.
+    pub fn inertia(&self) -> (usize, usize, usize)
+    {
+        let n = self.perm.len();
+        let scale = self.d.iter().fold(0.0f64, |acc, x| acc.max(x.abs()));
+        let tol = n as f64 * f64::EPSILON * scale;
+        let (mut positive, mut negative, mut zero) = (0, 0, 0);
+        let mut count = |x: f64| if x > tol { positive += 1 } else if x < -tol { negative += 1 } else { zero += 1 };
+
+        let mut k = 0;
+        while k < n
+        {
+            if k + 1 < n && self.d[(k + 1, k)] != 0.0
+            {
+                // Eigenvalues of the symmetric 2x2 block.
+                let (a, b, c) = (self.d[(k, k)], self.d[(k + 1, k)], self.d[(k + 1, k + 1)]);
+                let mean = 0.5 * (a + c);
+                let radius = (0.25 * (a - c) * (a - c) + b * b).sqrt();
+                count(mean + radius);
+                count(mean - radius);
+                k += 2;
+            }
+            else
+            {
+                count(self.d[(k, k)]);
+                k += 1;
+            }
+        }
+        (positive, negative, zero)
+    }
+}
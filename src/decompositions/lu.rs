@@ -2,6 +2,7 @@ use crate::matrix::Mat;
 use crate::vector::Vector;
 
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct LU<T>
 {
     pub compact: Mat<T>,
@@ -12,34 +13,12 @@ impl LU<f64>
     // Implement as triangular matrices instead.
     pub fn l(&self) -> Mat<f64>
     {
-        let mut lower = Mat::new((self.compact.rows, self.compact.cols));
-        for r in 0..self.compact.rows
-        {
-            for c in 0..r
-            {
-                let pos = (r, c);
-                lower[pos] =  self.compact[pos];
-            }  
-        }
-        for r in 0..self.compact.rows
-        {
-            lower[(r, r)] = 1.0
-        }
-        lower
+        unit_lower(&self.compact)
     }
 
     pub fn u(&self) -> Mat<f64>
     {
-        let mut upper = Mat::new((self.compact.rows, self.compact.cols));
-        for c in 0..self.compact.cols
-        {
-            for r in 0..=c
-            {
-                let pos = (r, c);
-                upper[pos] = self.compact[pos];
-            }
-        }
-        upper
+        upper(&self.compact)
     }
 
     pub fn split(&self) -> (Mat<f64>, Mat<f64>)
@@ -67,6 +46,38 @@ impl LU<f64>
 
 }
 
+pub(crate) fn unit_lower(compact: &Mat<f64>) -> Mat<f64>
+{
+    let mut lower = Mat::new((compact.rows, compact.cols));
+    for r in 0..compact.rows
+    {
+        for c in 0..r
+        {
+            let pos = (r, c);
+            lower[pos] = compact[pos];
+        }
+    }
+    for r in 0..compact.rows
+    {
+        lower[(r, r)] = 1.0
+    }
+    lower
+}
+
+pub(crate) fn upper(compact: &Mat<f64>) -> Mat<f64>
+{
+    let mut upper = Mat::new((compact.rows, compact.cols));
+    for c in 0..compact.cols
+    {
+        for r in 0..=c
+        {
+            let pos = (r, c);
+            upper[pos] = compact[pos];
+        }
+    }
+    upper
+}
+
 impl Mat<f64>
 {
     pub fn lu(mut self) -> LU<f64>
@@ -91,7 +102,7 @@ impl Mat<f64>
 
 impl LU<f64> 
 {
-    pub fn solve(&mut self, b: &Vector<f64>) -> Vector<f64>
+    pub fn solve(&self, b: &Vector<f64>) -> Vector<f64>
     {
         // Solves a.matmul(x) = b, add checks
         let n = b.len();
@@ -1,5 +1,8 @@
 use crate::{matrix::Mat, vector::Vector};
+use crate::permutation::Permutation;
+use crate::decompositions::lu::{unit_lower, upper};
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct LUP<T>
 {
     pub compact: Mat<T>,
@@ -9,38 +12,83 @@ pub struct LUP<T>
 
 impl Mat<f64>
 {
-    pub fn lup(mut self) -> LUP<f64> 
+    pub fn lup(self) -> LUP<f64> 
     {
-        
         assert!((self.rows == self.cols));
+        #[cfg(feature = "blas")]
+        if let Some(lup) = crate::blas::lup(&self)
+        {
+            return lup;
+        }
+        self.lup_blocked(64)
+    }
+
+    // Right-looking blocked lu, panels of block columns are factored with
+    // partial pivoting and the trailing matrix is updated with matmul.
+    pub fn lup_blocked(mut self, block: usize) -> LUP<f64>
+    {
+        assert!((self.rows == self.cols) && block > 0);
         let n = self.rows;
         let mut perm = (0..n).collect::<Vec<usize>>();
         let mut num_swaps: usize = 0;
 
-        for k in 0..n
+        for k0 in (0..n).step_by(block)
         {
-            let mut p = k;
-            for i in (k + 1)..n
+            let k1 = std::cmp::min(k0 + block, n);
+            for k in k0..k1
             {
-                if self[(i, k)].abs() > self[(p, k)].abs()
+                let mut p = k;
+                for i in (k + 1)..n
+                {
+                    if self[(i, k)].abs() > self[(p, k)].abs()
+                    {
+                        p = i;
+                    }
+                }
+                
+                if p != k
                 {
-                    p = i;
+                    self.swap_rows(k, p);
+                    perm.swap(k, p);
+                    num_swaps += 1;
+                }
+
+                for i in (k + 1)..n
+                {
+                    self[(i, k)] /= self[(k, k)];
+                    for j in (k + 1)..k1
+                    {
+                        self[(i, j)] -= self[(i, k)] * self[(k, j)];
+                    }
                 }
             }
-            
-            if p != k
+            if k1 == n
             {
-                self.swap_rows(k, p);
-                perm.swap(k, p);
-                num_swaps += 1;
+                break;
             }
 
-            for i in (k + 1)..n
+            // u12 = l11^-1 a12
+            for k in k0..k1
             {
-                self[(i, k)] /= self[(k, k)];
-                for j in (k+1)..n
+                for i in (k + 1)..k1
                 {
-                    self[(i, j)] -= self[(i, k)] * self[(k, j)];
+                    let lik = self[(i, k)];
+                    for j in k1..n
+                    {
+                        self[(i, j)] -= lik * self[(k, j)];
+                    }
+                }
+            }
+
+            // a22 -= l21 u12
+            let l21 = self.submat(k1, n, k0, k1).unwrap();
+            let u12 = self.submat(k0, k1, k1, n).unwrap();
+            let update = l21.matmul(&u12);
+            for i in k1..n
+            {
+                for j in k1..n
+                {
+                    self[(i, j)] -= update[(i - k1, j - k1)];
                 }
             }
         }
@@ -50,7 +98,22 @@ impl Mat<f64>
 
 impl LUP<f64>
 {
-    pub fn solve(&mut self, b: &Vector<f64>) -> Vector<f64>
+    pub fn p(&self) -> Permutation
+    {
+        Permutation::from_vec(self.perm.clone())
+    }
+
+    pub fn l(&self) -> Mat<f64>
+    {
+        unit_lower(&self.compact)
+    }
+
+    pub fn u(&self) -> Mat<f64>
+    {
+        upper(&self.compact)
+    }
+
+    pub fn solve(&self, b: &Vector<f64>) -> Vector<f64>
     {
         // Solves a.matmul(x) = b, add checks
         let mut x = Vector::new(b.len());
@@ -119,7 +182,7 @@ impl LUP<f64>
     pub fn det(&self) -> f64
     {
         let mut d: f64 = self.compact.iter_trace().product();
-        if self.num_swaps % 2 != 0
+        if !self.num_swaps.is_multiple_of(2)
         {
             d = -d;
         }
@@ -0,0 +1,185 @@
+use crate::matrix::Mat;
+use crate::permutation::Permutation;
+
+
+// p·a·qᵀ = l·u with l unit lower triangular and u upper trapezoidal, both
+// stored in compact. Column j of a·qᵀ is column col_perm[j] of a.
+pub struct LUPQ<T>
+{
+    pub compact: Mat<T>,
+    pub row_perm: Vec<usize>,
+    pub col_perm: Vec<usize>,
+}
+
+impl Mat<f64>
+{
+    pub fn lu_full_pivot(mut self) -> LUPQ<f64>
+    {
+        let (m, n) = self.shape();
+        let mut row_perm = (0..m).collect::<Vec<usize>>();
+        let mut col_perm = (0..n).collect::<Vec<usize>>();
+
+        for k in 0..std::cmp::min(m, n)
+        {
+            let (mut pr, mut pc) = (k, k);
+            for i in k..m
+            {
+                for j in k..n
+                {
+                    if self[(i, j)].abs() > self[(pr, pc)].abs()
+                    {
+                        pr = i;
+                        pc = j;
+                    }
+                }
+            }
+            if self[(pr, pc)] == 0.0
+            {
+                break;
+            }
+
+            if pr != k
+            {
+                self.swap_rows(k, pr);
+                row_perm.swap(k, pr);
+            }
+            if pc != k
+            {
+                for i in 0..m
+                {
+                    let temp = self[(i, k)];
+                    self[(i, k)] = self[(i, pc)];
+                    self[(i, pc)] = temp;
+                }
+                col_perm.swap(k, pc);
+            }
+
+            for i in (k + 1)..m
+            {
+                self[(i, k)] /= self[(k, k)];
+                for j in (k + 1)..n
+                {
+                    self[(i, j)] -= self[(i, k)] * self[(k, j)];
+                }
+            }
+        }
+        LUPQ { compact: self, row_perm, col_perm }
+    }
+}
+
+impl LUPQ<f64>
+{
+    pub fn p(&self) -> Permutation
+    {
+        Permutation::from_vec(self.row_perm.clone())
+    }
+
+    pub fn q(&self) -> Permutation
+    {
+        Permutation::from_vec(self.col_perm.clone())
+    }
+
+    pub fn l(&self) -> Mat<f64>
+    {
+        let (m, n) = self.compact.shape();
+        let k = std::cmp::min(m, n);
+        let mut lower = Mat::new((m, k));
+        for r in 0..m
+        {
+            for c in 0..std::cmp::min(r, k)
+            {
+                lower[(r, c)] = self.compact[(r, c)];
+            }
+            if r < k
+            {
+                lower[(r, r)] = 1.0;
+            }
+        }
+        lower
+    }
+
+    pub fn u(&self) -> Mat<f64>
+    {
+        let (m, n) = self.compact.shape();
+        let k = std::cmp::min(m, n);
+        let mut upper = Mat::new((k, n));
+        for r in 0..k
+        {
+            for c in r..n
+            {
+                upper[(r, c)] = self.compact[(r, c)];
+            }
+        }
+        upper
+    }
+
+    // Number of pivots larger than tol relative to the largest pivot.
+    pub fn rank(&self, tol: f64) -> usize
+    {
+        let largest = self.compact.iter_trace().next().map_or(0.0, |x| x.abs());
+        self.compact.iter_trace().take_while(|x| x.abs() > tol * largest).count()
+    }
+
+    // Columns form a basis of the null space of a.
+    pub fn kernel(&self) -> Mat<f64>
+    {
+        let n = self.compact.cols;
+        let r = self.rank(self._default_tol());
+
+        // u11 y1 + u12 z = 0 for each unit vector z.
+        let mut basis = Mat::new((n, n - r));
+        for k in 0..(n - r)
+        {
+            let mut y = vec![0.0; n];
+            y[r + k] = 1.0;
+            for i in (0..r).rev()
+            {
+                let mut sum = self.compact[(i, r + k)];
+                for (j, yj) in y.iter().enumerate().take(r).skip(i + 1)
+                {
+                    sum += self.compact[(i, j)] * yj;
+                }
+                y[i] = -sum / self.compact[(i, i)];
+            }
+            for (j, &c) in self.col_perm.iter().enumerate()
+            {
+                basis[(c, k)] = y[j];
+            }
+        }
+        basis
+    }
+
+    // Columns form a basis of the column space of a, they are the pivot
+    // columns of a.
+    pub fn image(&self) -> Mat<f64>
+    {
+        let m = self.compact.rows;
+        let r = self.rank(self._default_tol());
+        let mut basis = Mat::new((m, r));
+        for j in 0..r
+        {
+            for i in 0..m
+            {
+                let mut sum = 0.0;
+                for k in 0..=std::cmp::min(i, j)
+                {
+                    let lik = if k == i { 1.0 } else { self.compact[(i, k)] };
+                    sum += lik * self.compact[(k, j)];
+                }
+                basis[(self.row_perm[i], j)] = sum;
+            }
+        }
+        basis
+    }
+
+    pub fn det(&self) -> f64
+    {
+        assert!(self.compact.rows == self.compact.cols);
+        self.p().sign() * self.q().sign() * self.compact.iter_trace().product::<f64>()
+    }
+
+    fn _default_tol(&self) -> f64
+    {
+        std::cmp::max(self.compact.rows, self.compact.cols) as f64 * f64::EPSILON
+    }
+}
@@ -1,4 +1,11 @@
 pub mod lu;
 pub mod lup;
+pub mod lupq;
 pub mod qr;
-pub mod eig;
\ No newline at end of file
+pub mod eig;
+pub mod schur;
+pub mod cholesky;
+pub mod generalized;
+pub mod ldl;
+pub mod givens;
+pub(crate) mod householder;
\ No newline at end of file
@@ -1,131 +1,438 @@
 use crate::matrix::Mat;
 use crate::vector::Vector;
+use crate::decompositions::householder::{_reflector, _reflect_rows};
+use crate::decompositions::givens::Givens;
 
 
+// Householder vectors are stored below the diagonal of compact with an
+// implicit leading one, r on and above it. q = h0 h1 ... with
+// hi = I - tau[i] vi viᵀ. Updating the factorization switches to an
+// explicit q, compact then only holds r and tau is empty.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct QR<T>
 {
+    pub compact: Mat<T>,
+    pub tau: Vec<T>,
+    pub explicit_q: Option<Mat<T>>,
+} 
+
+pub struct LQ<T>
+{
+    pub l: Mat<T>,
     pub q: Mat<T>,
+}
+
+pub struct QL<T>
+{
+    pub q: Mat<T>,
+    pub l: Mat<T>,
+}
+
+pub struct RQ<T>
+{
     pub r: Mat<T>,
-} 
+    pub q: Mat<T>,
+}
 
 
 impl Mat<f64>
 {
     pub fn qr(mut self) -> QR<f64>
     {
+        #[cfg(feature = "blas")]
+        if let Some(qr) = crate::blas::qr(&self)
+        {
+            return qr;
+        }
         let (m, n) = self.shape();
-        let mut q = Mat::eye(m);
-        let mut h = Mat::eye(m);
-        let mut v = Vector::<f64>::new(m);
+        let k = std::cmp::min(m, n);
+        let mut tau = Vec::with_capacity(k);
+        for i in 0..k
+        {
+            let x = (i..m).map(|r| self[(r, i)]).collect::<Vec<f64>>();
+            let (mut v, mut t) = _reflector(&x);
+            if t != 0.0
+            {
+                let v0 = v[0];
+                v.iter_mut().for_each(|vi| *vi /= v0);
+                t *= v0 * v0;
+            }
+            _reflect_rows(&mut self, &v, t, i, i);
+            for r in (i + 1)..m
+            {
+                self[(r, i)] = v[r - i];
+            }
+            tau.push(t);
+        }
+        QR::<f64> {compact: self, tau, explicit_q: None}
+    }
+
+    // Economy qr, q of shape m x k and r of shape k x n with k = min(m, n).
+    pub fn thin_qr(self) -> (Mat<f64>, Mat<f64>)
+    {
+        let qr = self.qr();
+        (qr.thin_q(), qr.thin_r())
+    }
+
+    // a = l q with l lower trapezoidal and q orthogonal.
+    pub fn lq(self) -> LQ<f64>
+    {
+        let qr = self.transposed().qr();
+        LQ::<f64> {l: qr.r().transposed(), q: qr.q().transposed()}
+    }
+
+    // Economy lq with l of shape m x k and q of shape k x n, k = min(m, n).
+    pub fn thin_lq(self) -> LQ<f64>
+    {
+        let (q, r) = self.transposed().thin_qr();
+        LQ::<f64> {l: r.transposed(), q: q.transposed()}
+    }
+
+    // a = q l with l lower trapezoidal, from the qr of a with rows and
+    // columns reversed.
+    pub fn ql(self) -> QL<f64>
+    {
+        let qr = _reversed(&self).qr();
+        QL::<f64> {q: _reversed(&qr.q()), l: _reversed(&qr.r())}
+    }
+
+    // a = r q with r upper trapezoidal.
+    pub fn rq(self) -> RQ<f64>
+    {
+        let ql = self.transposed().ql();
+        RQ::<f64> {r: ql.l.transposed(), q: ql.q.transposed()}
+    }
+}
+
+// Reverses the order of both the rows and the columns.
+fn _reversed(mat: &Mat<f64>) -> Mat<f64>
+{
+    let (m, n) = mat.shape();
+    let mut reversed = Mat::new((m, n));
+    for r in 0..m
+    {
+        for c in 0..n
+        {
+            reversed[(r, c)] = mat[(m - 1 - r, n - 1 - c)];
+        }
+    }
+    reversed
+}
 
+impl QR<f64> 
+{
+    pub fn q(&self) -> Mat<f64>
+    {
+        match &self.explicit_q
+        {
+            Some(q) => q.clone(),
+            None => self.apply_q(&Mat::eye(self.compact.rows)),
+        }
+    }
+
+    pub fn r(&self) -> Mat<f64>
+    {
+        let (m, n) = self.compact.shape();
+        let mut r = Mat::new((m, n));
         for i in 0..std::cmp::min(m, n)
         {
-            Self::_householder(&mut h, &mut self, &mut v, m, i);
-            q._inplace_partial_matmul(&h, i);
-            
-            self.transpose();
-            h.transpose();
-            self._inplace_partial_matmul(&h, i);
-            self.transpose();
-            h.transpose();
+            for j in i..n
+            {
+                r[(i, j)] = self.compact[(i, j)];
+            }
         }
-        QR::<f64> {q, r: self}
+        r
     }
 
-    fn _householder(h: &mut Mat<f64>, r: &mut Mat<f64>, v: &mut Vector<f64>, m: usize, i: usize)
+    // First min(m, n) columns of q.
+    pub fn thin_q(&self) -> Mat<f64>
     {
-        let mut anorm = 0.0;
+        let (m, n) = self.compact.shape();
+        let k = std::cmp::min(m, n);
+        let mut q = Mat::new((m, k));
+        for i in 0..k
+        {
+            q[(i, i)] = 1.0;
+        }
+        self.apply_q(&q)
+    }
 
-        for j in i..m
+    // First min(m, n) rows of r.
+    pub fn thin_r(&self) -> Mat<f64>
+    {
+        let (m, n) = self.compact.shape();
+        self.r().submat(0, std::cmp::min(m, n), 0, n).unwrap()
+    }
+
+    pub fn apply_q(&self, b: &Mat<f64>) -> Mat<f64>
+    {
+        assert!(b.rows == self.compact.rows);
+        if let Some(q) = &self.explicit_q
         {
-            let elem = r[(j, i)];
-            anorm += elem * elem;
-        } 
+            return q.matmul(b);
+        }
+        let mut b = b.clone();
+        for i in (0..self.tau.len()).rev()
+        {
+            _reflect_rows(&mut b, &self._reflector(i), self.tau[i], i, 0);
+        }
+        b
+    }
 
-        v[i] = r[(i, i)] - anorm.sqrt();
-        for j in (i+1)..m
+    pub fn apply_qt(&self, b: &Mat<f64>) -> Mat<f64>
+    {
+        assert!(b.rows == self.compact.rows);
+        if let Some(q) = &self.explicit_q
         {
-            v[j] = r[(j, i)];
+            return q.transposed().matmul(b);
         }
-        
-        let mut vnorm = 0.0; 
-        for j in i..m
+        let mut b = b.clone();
+        for i in 0..self.tau.len()
+        {
+            _reflect_rows(&mut b, &self._reflector(i), self.tau[i], i, 0);
+        }
+        b
+    }
+
+    pub fn apply_q_vec(&self, b: &Vector<f64>) -> Vector<f64>
+    {
+        assert!(b.len() == self.compact.rows);
+        if let Some(q) = &self.explicit_q
         {
-            vnorm += v[j] * v[j];
+            return q.vecmul(b);
         }
-    
-        if vnorm != 0.0
+        let mut b = b.clone();
+        for i in (0..self.tau.len()).rev()
         {
-            let inv_sqrt = 1.0 / vnorm.sqrt();
-            for j in i..m
-            {
-                v[j] *= inv_sqrt;
-            }
+            self._reflect_vec(&mut b, i);
+        }
+        b
+    }
+
+    pub fn apply_qt_vec(&self, b: &Vector<f64>) -> Vector<f64>
+    {
+        assert!(b.len() == self.compact.rows);
+        if let Some(q) = &self.explicit_q
+        {
+            return q.transposed().vecmul(b);
+        }
+        let mut b = b.clone();
+        for i in 0..self.tau.len()
+        {
+            self._reflect_vec(&mut b, i);
         }
+        b
+    }
 
-        for j in i..m
+    // Factorization of a + u vᵀ.
+    pub fn update(&mut self, u: &Vector<f64>, v: &Vector<f64>)
+    {
+        let (m, n) = self.compact.shape();
+        assert!(u.len() == m && v.len() == n);
+        self._make_explicit();
+        let mut w = self.apply_qt_vec(u);
+        let (q, r) = (self.explicit_q.as_mut().unwrap(), &mut self.compact);
+
+        // Rotate w onto the first unit vector, r becomes upper Hessenberg.
+        for k in (1..m).rev()
+        {
+            let g = Givens::new(w[k - 1], w[k]);
+            g.rotate_vec(&mut w, k - 1, k);
+            g.rotate_rows(r, k - 1, k, k - 1);
+            g.rotate_cols(q, k - 1, k, m);
+        }
+        for j in 0..n
         {
-            for k in i..m
+            r[(0, j)] += w[0] * v[j];
+        }
+        _retriangularize(q, r, 0);
+    }
+
+    // Factorization of a with row inserted before row k.
+    pub fn insert_row(&mut self, k: usize, row: &Vector<f64>)
+    {
+        let (m, n) = self.compact.shape();
+        assert!(k <= m && row.len() == n);
+        self._make_explicit();
+        let q = self.explicit_q.as_ref().unwrap();
+
+        // [row; a] = diag(1, q) [row; r], then move the first row to k.
+        let mut r = Mat::new((m + 1, n));
+        let mut big = Mat::new((m + 1, m + 1));
+        for j in 0..n
+        {
+            r[(0, j)] = row[j];
+        }
+        for i in 0..m
+        {
+            for j in 0..n
             {
-                h[(j, k)] = -2.0 * v[j] * v[k];
+                r[(i + 1, j)] = self.compact[(i, j)];
             }
         }
-        
-        for j in i..m
+        big[(k, 0)] = 1.0;
+        for i in 0..m
         {
-            h[(j, j)] += 1.0;
+            let target = if i < k { i } else { i + 1 };
+            for j in 0..m
+            {
+                big[(target, j + 1)] = q[(i, j)];
+            }
         }
+        _retriangularize(&mut big, &mut r, 0);
+        self.compact = r;
+        self.explicit_q = Some(big);
+    }
+
+    // Factorization of a with row k removed.
+    pub fn delete_row(&mut self, k: usize)
+    {
+        let (m, n) = self.compact.shape();
+        assert!(k < m && m > 1);
+        self._make_explicit();
+        let (q, r) = (self.explicit_q.as_mut().unwrap(), &mut self.compact);
 
+        // Rotate row k of q onto the first unit vector, then drop it
+        // together with the first column of q and the first row of r.
+        for j in (1..m).rev()
+        {
+            let g = Givens::new(q[(k, j - 1)], q[(k, j)]);
+            g.rotate_cols(q, j - 1, j, m);
+            g.rotate_rows(r, j - 1, j, j - 1);
+        }
+        let mut smaller = Mat::new((m - 1, m - 1));
+        for i in 0..(m - 1)
+        {
+            let source = if i < k { i } else { i + 1 };
+            for j in 0..(m - 1)
+            {
+                smaller[(i, j)] = q[(source, j + 1)];
+            }
+        }
+        self.compact = r.submat(1, m, 0, n).unwrap();
+        self.explicit_q = Some(smaller);
     }
 
-    fn _inplace_partial_matmul(&mut self, rhs: &Mat<f64>, start_col: usize)
+    // Factorization of a with col inserted before column k.
+    pub fn insert_col(&mut self, k: usize, col: &Vector<f64>)
     {
-        let mut cache = Vector::<f64>::new(self.cols-start_col);
-        for i in 0..self.rows
+        let (m, n) = self.compact.shape();
+        assert!(k <= n && col.len() == m);
+        self._make_explicit();
+        let w = self.apply_qt_vec(col);
+
+        let mut r = Mat::new((m, n + 1));
+        for i in 0..m
         {
-            for j in start_col..self.cols
+            for j in 0..n
             {
-                cache[j-start_col] = self[(i, j)]; 
+                r[(i, if j < k { j } else { j + 1 })] = self.compact[(i, j)];
             }
+            r[(i, k)] = w[i];
+        }
+        let q = self.explicit_q.as_mut().unwrap();
+        for j in ((k + 1)..m).rev()
+        {
+            let g = Givens::new(r[(j - 1, k)], r[(j, k)]);
+            g.rotate_rows(&mut r, j - 1, j, k);
+            g.rotate_cols(q, j - 1, j, m);
+            r[(j, k)] = 0.0;
+        }
+        self.compact = r;
+    }
+
+    // Factorization of a with column k removed.
+    pub fn delete_col(&mut self, k: usize)
+    {
+        let (m, n) = self.compact.shape();
+        assert!(k < n);
+        self._make_explicit();
 
-            for j in start_col..rhs.cols
+        let mut r = Mat::new((m, n - 1));
+        for i in 0..m
+        {
+            for j in 0..(n - 1)
             {
-                self[(i, j)] = 0.0;
-                for k in start_col..self.cols
-                {
-                    self[(i, j)] += cache[k-start_col] * rhs[(k, j)];
-                }
+                r[(i, j)] = self.compact[(i, if j < k { j } else { j + 1 })];
             }
         }
+        _retriangularize(self.explicit_q.as_mut().unwrap(), &mut r, k);
+        self.compact = r;
     }
-}
 
-impl QR<f64> 
-{
-    pub fn solve(&mut self, b: &Vector<f64>) -> Vector<f64>
+    pub fn solve(&self, b: &Vector<f64>) -> Vector<f64>
     {
-        // Solves a.matmul(x) = b, add checks
+        // Solves a.matmul(x) = b for square a.
         let n = b.len();
+        assert!(self.compact.shape() == (n, n));
+        let y = self.apply_qt_vec(b);
         let mut x = Vector::<f64>::new(n);
-        self.q.transpose();
-        let y = self.q.vecmul(&b);
-        self.q.transpose();
         
-        for i in (0..=(n-1)).rev() 
+        for i in (0..n).rev() 
         {
             let mut sum = 0.0;
             for j in (i+1)..n
             {
-                sum += self.r[(i, j)] * x[j];
+                sum += self.compact[(i, j)] * x[j];
             }
-            x[i] = (y[i] - sum) / self.r[(i, i)];
+            x[i] = (y[i] - sum) / self.compact[(i, i)];
         }
         x
-        
     }
 
     pub fn det(&self) -> f64
     {
-        (self.q.iter_trace().product::<f64>()).signum() * self.r.iter_trace().product::<f64>()
+        let sign = match &self.explicit_q
+        {
+            Some(q) => q.clone().lup().det().signum(),
+            None =>
+            {
+                // Every nontrivial reflector has determinant -1.
+                let reflections = self.tau.iter().filter(|&&t| t != 0.0).count();
+                if reflections.is_multiple_of(2) { 1.0 } else { -1.0 }
+            }
+        };
+        sign * self.compact.iter_trace().product::<f64>()
+    }
+
+    fn _make_explicit(&mut self)
+    {
+        if self.explicit_q.is_none()
+        {
+            self.explicit_q = Some(self.q());
+            self.compact = self.r();
+            self.tau.clear();
+        }
+    }
+
+    fn _reflector(&self, i: usize) -> Vec<f64>
+    {
+        let mut v = vec![1.0];
+        v.extend((i + 1..self.compact.rows).map(|r| self.compact[(r, i)]));
+        v
+    }
+
+    fn _reflect_vec(&self, b: &mut Vector<f64>, i: usize)
+    {
+        let v = self._reflector(i);
+        let dot = v.iter().enumerate().map(|(k, vk)| vk * b[i + k]).sum::<f64>();
+        for (k, vk) in v.iter().enumerate()
+        {
+            b[i + k] -= self.tau[i] * dot * vk;
+        }
+    }
+}
+
+// Zeros the subdiagonal of an upper Hessenberg r from column start on,
+// accumulating the rotations into q.
+fn _retriangularize(q: &mut Mat<f64>, r: &mut Mat<f64>, start: usize)
+{
+    let (m, n) = r.shape();
+    for j in start..std::cmp::min(m.saturating_sub(1), n)
+    {
+        let g = Givens::new(r[(j, j)], r[(j + 1, j)]);
+        g.rotate_rows(r, j, j + 1, j);
+        g.rotate_cols(q, j, j + 1, m);
+        r[(j + 1, j)] = 0.0;
     }
 }
\ No newline at end of file
@@ -0,0 +1,465 @@
+use crate::matrix::Mat;
+use crate::vector::Vector;
+use crate::decompositions::householder::{_reflector, _reflect_rows, _reflect_cols};
+
+
+impl Mat<f64>
+{
+    // self = q.matmul(&h).matmul(&q.transposed()) with h upper Hessenberg.
+    pub fn hessenberg(mut self) -> (Mat<f64>, Mat<f64>)
+    {
+        assert!(self.rows == self.cols);
+        let n = self.rows;
+        let mut q = Mat::eye(n);
+
+        for k in 0..n.saturating_sub(2)
+        {
+            let x = ((k + 1)..n).map(|i| self[(i, k)]).collect::<Vec<f64>>();
+            let (v, tau) = _reflector(&x);
+            _reflect_rows(&mut self, &v, tau, k + 1, k);
+            _reflect_cols(&mut self, &v, tau, k + 1, n);
+            _reflect_cols(&mut q, &v, tau, k + 1, n);
+            for i in (k + 2)..n
+            {
+                self[(i, k)] = 0.0;
+            }
+        }
+        (q, self)
+    }
+
+    // Real Schur form, self = z.matmul(&t).matmul(&z.transposed()) with t
+    // upper triangular apart from 2x2 blocks for complex conjugate eigenvalues.
+    pub fn schur(self) -> Result<(Mat<f64>, Mat<f64>), &'static str>
+    {
+        let (mut z, mut t) = self.hessenberg();
+        hessenberg_qr(&mut t, &mut z)?;
+        Ok((z, t))
+    }
+
+    // Real Schur form with the eigenvalues for which select(re, im) holds moved
+    // to the leading block. Also returns the size of that block.
+    pub fn ordered_schur<F>(self, select: F) -> Result<(Mat<f64>, Mat<f64>, usize), &'static str>
+    where F: Fn(f64, f64) -> bool
+    {
+        let (mut z, mut t) = self.schur()?;
+        let n = t.rows;
+        let mut selected = 0;
+        let mut i = 0;
+        while i < n
+        {
+            let size = if i + 1 < n && t[(i + 1, i)] != 0.0 { 2 } else { 1 };
+            let (re, im) = _block_eigval(&t, i, size);
+            if select(re, im)
+            {
+                let mut pos = i;
+                while pos > selected
+                {
+                    let prev = if pos >= 2 && t[(pos - 1, pos - 2)] != 0.0 { 2 } else { 1 };
+                    _swap_blocks(&mut t, &mut z, pos - prev, prev, size);
+                    pos -= prev;
+                }
+                selected += size;
+            }
+            i += size;
+        }
+        Ok((z, t, selected))
+    }
+}
+
+// One eigenvalue (re, im) of the diagonal block at k, im >= 0.
+fn _block_eigval(t: &Mat<f64>, k: usize, size: usize) -> (f64, f64)
+{
+    if size == 1
+    {
+        return (t[(k, k)], 0.0);
+    }
+    let (a, b, c, d) = (t[(k, k)], t[(k, k + 1)], t[(k + 1, k)], t[(k + 1, k + 1)]);
+    let half = 0.5 * (a + d);
+    let disc = 0.25 * (a - d) * (a - d) + b * c;
+    (half, (-disc).max(0.0).sqrt())
+}
+
+// Swaps the adjacent diagonal blocks of sizes p and q starting at k.
+fn _swap_blocks(t: &mut Mat<f64>, z: &mut Mat<f64>, k: usize, p: usize, q: usize)
+{
+    let m = p + q;
+
+    // t11 x - x t22 = -t12, so the columns of [x; I] span the t22 subspace.
+    let mut kron = Mat::new((p * q, p * q));
+    let mut rhs = Vector::new(p * q);
+    for c in 0..q
+    {
+        for r in 0..p
+        {
+            let row = c * p + r;
+            for l in 0..p
+            {
+                kron[(row, c * p + l)] += t[(k + r, k + l)];
+            }
+            for l in 0..q
+            {
+                kron[(row, l * p + r)] -= t[(k + p + l, k + p + c)];
+            }
+            rhs[row] = -t[(k + r, k + p + c)];
+        }
+    }
+    let x = kron.lup().solve(&rhs);
+
+    let mut basis = Mat::new((m, q));
+    for c in 0..q
+    {
+        for r in 0..p
+        {
+            basis[(r, c)] = x[c * p + r];
+        }
+        basis[(p + c, c)] = 1.0;
+    }
+    let u = basis.qr().q();
+
+    _rotate_block(t, z, k, &u);
+    for r in q..m
+    {
+        for c in 0..q
+        {
+            t[(k + r, k + c)] = 0.0;
+        }
+    }
+}
+
+// t = uᵀ t u and z = z u for u acting on the indices k..k+u.rows.
+pub(crate) fn _rotate_block(t: &mut Mat<f64>, z: &mut Mat<f64>, k: usize, u: &Mat<f64>)
+{
+    let n = t.rows;
+    let m = u.rows;
+    let mut cache = vec![0.0; m];
+    for col in k..n
+    {
+        for (r, c) in cache.iter_mut().enumerate()
+        {
+            *c = t[(k + r, col)];
+        }
+        for r in 0..m
+        {
+            t[(k + r, col)] = (0..m).map(|l| u[(l, r)] * cache[l]).sum();
+        }
+    }
+    for row in 0..std::cmp::min(n, k + m)
+    {
+        for (c, v) in cache.iter_mut().enumerate()
+        {
+            *v = t[(row, k + c)];
+        }
+        for c in 0..m
+        {
+            t[(row, k + c)] = (0..m).map(|l| cache[l] * u[(l, c)]).sum();
+        }
+    }
+    for row in 0..z.rows
+    {
+        for (c, v) in cache.iter_mut().enumerate()
+        {
+            *v = z[(row, k + c)];
+        }
+        for c in 0..m
+        {
+            z[(row, k + c)] = (0..m).map(|l| cache[l] * u[(l, c)]).sum();
+        }
+    }
+}
+
+// Francis double shift QR on an upper Hessenberg matrix (after EISPACK hqr2).
+// Overwrites h with the real Schur form T, accumulates the transformations
+// into z and returns the real and imaginary parts of the eigenvalues.
+pub(crate) fn hessenberg_qr(h: &mut Mat<f64>, z: &mut Mat<f64>) -> Result<(Vector<f64>, Vector<f64>), &'static str>
+{
+    assert!(h.rows == h.cols && z.cols == h.rows);
+    let nn = h.rows;
+    let mut wr = Vector::<f64>::new(nn);
+    let mut wi = Vector::<f64>::new(nn);
+    let eps = f64::EPSILON;
+    let mut exshift = 0.0;
+    let (mut p, mut q, mut r, mut s, mut w, mut x, mut y, mut zz);
+
+    let mut norm = 0.0;
+    for i in 0..nn
+    {
+        for j in i.saturating_sub(1)..nn
+        {
+            norm += h[(i, j)].abs();
+        }
+    }
+
+    let mut n = nn as isize - 1;
+    let mut iter = 0;
+    while n >= 0
+    {
+        let nu = n as usize;
+
+        // Look for a single small sub-diagonal element.
+        let mut l = nu;
+        while l > 0
+        {
+            s = h[(l - 1, l - 1)].abs() + h[(l, l)].abs();
+            if s == 0.0
+            {
+                s = norm;
+            }
+            if h[(l, l - 1)].abs() < eps * s
+            {
+                break;
+            }
+            l -= 1;
+        }
+
+        if l == nu
+        {
+            // One root found.
+            if l > 0
+            {
+                h[(l, l - 1)] = 0.0;
+            }
+            h[(nu, nu)] += exshift;
+            wr[nu] = h[(nu, nu)];
+            wi[nu] = 0.0;
+            n -= 1;
+            iter = 0;
+        }
+        else if l == nu - 1
+        {
+            // Two roots found.
+            if l > 0
+            {
+                h[(l, l - 1)] = 0.0;
+            }
+            w = h[(nu, nu - 1)] * h[(nu - 1, nu)];
+            p = (h[(nu - 1, nu - 1)] - h[(nu, nu)]) / 2.0;
+            q = p * p + w;
+            zz = q.abs().sqrt();
+            h[(nu, nu)] += exshift;
+            h[(nu - 1, nu - 1)] += exshift;
+            x = h[(nu, nu)];
+
+            if q >= 0.0
+            {
+                // Real pair, rotate the block to upper triangular form.
+                zz = if p >= 0.0 { p + zz } else { p - zz };
+                wr[nu - 1] = x + zz;
+                wr[nu] = wr[nu - 1];
+                if zz != 0.0
+                {
+                    wr[nu] = x - w / zz;
+                }
+                wi[nu - 1] = 0.0;
+                wi[nu] = 0.0;
+                x = h[(nu, nu - 1)];
+                s = x.abs() + zz.abs();
+                p = x / s;
+                q = zz / s;
+                r = (p * p + q * q).sqrt();
+                p /= r;
+                q /= r;
+
+                for j in (nu - 1)..nn
+                {
+                    zz = h[(nu - 1, j)];
+                    h[(nu - 1, j)] = q * zz + p * h[(nu, j)];
+                    h[(nu, j)] = q * h[(nu, j)] - p * zz;
+                }
+                for i in 0..=nu
+                {
+                    zz = h[(i, nu - 1)];
+                    h[(i, nu - 1)] = q * zz + p * h[(i, nu)];
+                    h[(i, nu)] = q * h[(i, nu)] - p * zz;
+                }
+                for i in 0..z.rows
+                {
+                    zz = z[(i, nu - 1)];
+                    z[(i, nu - 1)] = q * zz + p * z[(i, nu)];
+                    z[(i, nu)] = q * z[(i, nu)] - p * zz;
+                }
+                h[(nu, nu - 1)] = 0.0;
+            }
+            else
+            {
+                // Complex pair.
+                wr[nu - 1] = x + p;
+                wr[nu] = x + p;
+                wi[nu - 1] = zz;
+                wi[nu] = -zz;
+            }
+            n -= 2;
+            iter = 0;
+        }
+        else
+        {
+            // Form shift.
+            x = h[(nu, nu)];
+            y = h[(nu - 1, nu - 1)];
+            w = h[(nu, nu - 1)] * h[(nu - 1, nu)];
+
+            // Exceptional shifts.
+            if iter == 10
+            {
+                exshift += x;
+                for i in 0..=nu
+                {
+                    h[(i, i)] -= x;
+                }
+                s = h[(nu, nu - 1)].abs() + h[(nu - 1, nu - 2)].abs();
+                x = 0.75 * s;
+                y = x;
+                w = -0.4375 * s * s;
+            }
+            if iter == 30
+            {
+                s = (y - x) / 2.0;
+                s = s * s + w;
+                if s > 0.0
+                {
+                    s = s.sqrt();
+                    if y < x
+                    {
+                        s = -s;
+                    }
+                    s = x - w / ((y - x) / 2.0 + s);
+                    for i in 0..=nu
+                    {
+                        h[(i, i)] -= s;
+                    }
+                    exshift += s;
+                    x = 0.964;
+                    y = x;
+                    w = x;
+                }
+            }
+            iter += 1;
+            if iter >= 1000
+            {
+                return Err("Schur iteration did not converge.");
+            }
+
+            // Look for two consecutive small sub-diagonal elements.
+            let mut m = nu - 2;
+            loop
+            {
+                zz = h[(m, m)];
+                r = x - zz;
+                s = y - zz;
+                p = (r * s - w) / h[(m + 1, m)] + h[(m, m + 1)];
+                q = h[(m + 1, m + 1)] - zz - r - s;
+                r = h[(m + 2, m + 1)];
+                s = p.abs() + q.abs() + r.abs();
+                p /= s;
+                q /= s;
+                r /= s;
+                if m == l
+                {
+                    break;
+                }
+                if h[(m, m - 1)].abs() * (q.abs() + r.abs())
+                    < eps * (p.abs() * (h[(m - 1, m - 1)].abs() + zz.abs() + h[(m + 1, m + 1)].abs()))
+                {
+                    break;
+                }
+                m -= 1;
+            }
+
+            for i in (m + 2)..=nu
+            {
+                h[(i, i - 2)] = 0.0;
+                if i > m + 2
+                {
+                    h[(i, i - 3)] = 0.0;
+                }
+            }
+
+            // Double QR step involving rows l..=n and columns m..=n.
+            for k in m..nu
+            {
+                let notlast = k != nu - 1;
+                if k != m
+                {
+                    p = h[(k, k - 1)];
+                    q = h[(k + 1, k - 1)];
+                    r = if notlast { h[(k + 2, k - 1)] } else { 0.0 };
+                    x = p.abs() + q.abs() + r.abs();
+                    if x == 0.0
+                    {
+                        continue;
+                    }
+                    p /= x;
+                    q /= x;
+                    r /= x;
+                }
+
+                s = (p * p + q * q + r * r).sqrt();
+                if p < 0.0
+                {
+                    s = -s;
+                }
+                if s != 0.0
+                {
+                    if k != m
+                    {
+                        h[(k, k - 1)] = -s * x;
+                    }
+                    else if l != m
+                    {
+                        h[(k, k - 1)] = -h[(k, k - 1)];
+                    }
+                    p += s;
+                    x = p / s;
+                    y = q / s;
+                    zz = r / s;
+                    q /= p;
+                    r /= p;
+
+                    for j in k..nn
+                    {
+                        p = h[(k, j)] + q * h[(k + 1, j)];
+                        if notlast
+                        {
+                            p += r * h[(k + 2, j)];
+                            h[(k + 2, j)] -= p * zz;
+                        }
+                        h[(k, j)] -= p * x;
+                        h[(k + 1, j)] -= p * y;
+                    }
+
+                    for i in 0..=std::cmp::min(nu, k + 3)
+                    {
+                        p = x * h[(i, k)] + y * h[(i, k + 1)];
+                        if notlast
+                        {
+                            p += zz * h[(i, k + 2)];
+                            h[(i, k + 2)] -= p * r;
+                        }
+                        h[(i, k)] -= p;
+                        h[(i, k + 1)] -= p * q;
+                    }
+
+                    for i in 0..z.rows
+                    {
+                        p = x * z[(i, k)] + y * z[(i, k + 1)];
+                        if notlast
+                        {
+                            p += zz * z[(i, k + 2)];
+                            z[(i, k + 2)] -= p * r;
+                        }
+                        z[(i, k)] -= p;
+                        z[(i, k + 1)] -= p * q;
+                    }
+                }
+            }
+        }
+    }
+
+    for i in 0..nn
+    {
+        for j in 0..i.saturating_sub(1)
+        {
+            h[(i, j)] = 0.0;
+        }
+    }
+    Ok((wr, wi))
+}
@@ -0,0 +1,95 @@
+use crate::matrix::Mat;
+use crate::vector::Vector;
+use crate::eigensolvers::{LinearOperator, ShiftInvert, _start_vector};
+
+
+pub struct EigPair
+{
+    pub eigval: f64,
+    pub eigvec: Vector<f64>,
+    pub residual: f64,
+    pub iterations: usize,
+}
+
+// All iterations stop once |A x - λ x| <= tol for the unit vector x.
+pub fn power_iteration<A>(op: &A, tol: f64, max_iter: usize) -> EigPair
+where A: LinearOperator + ?Sized
+{
+    let mut x = _start_vector(op.dim(), 0);
+    x /= x.mag();
+    let mut pair = EigPair { eigval: 0.0, eigvec: x, residual: f64::INFINITY, iterations: 0 };
+
+    // The eigenvalue and residual always belong to the returned vector.
+    let mut y = op.apply(&pair.eigvec);
+    loop
+    {
+        pair.eigval = pair.eigvec.dot(&y);
+        pair.residual = (y.clone() - pair.eigvec.clone() * pair.eigval).mag();
+        if pair.residual <= tol || pair.iterations == max_iter
+        {
+            break;
+        }
+        // y = 0 has a zero residual and stops above, so the vector x of the
+        // null space comes back with eigenvalue 0 instead of a NaN.
+        pair.eigvec = y.clone() / y.mag();
+        pair.iterations += 1;
+        y = op.apply(&pair.eigvec);
+    }
+    pair
+}
+
+// Finds the eigenpair with eigenvalue closest to shift.
+pub fn inverse_iteration(a: &Mat<f64>, shift: f64, tol: f64, max_iter: usize) -> EigPair
+{
+    let inverted = ShiftInvert::new(a, shift);
+    let mut x = _start_vector(a.rows, 0);
+    x /= x.mag();
+    let mut pair = EigPair { eigval: 0.0, eigvec: x, residual: f64::INFINITY, iterations: 0 };
+
+    for _ in 0..max_iter
+    {
+        let y = inverted.apply(&pair.eigvec);
+        pair.eigvec = y.clone() / y.mag();
+        pair.iterations += 1;
+        if _update(a, &mut pair) <= tol
+        {
+            break;
+        }
+    }
+    pair
+}
+
+// Refines an approximate eigenvector, converging cubically for symmetric a.
+pub fn rayleigh_quotient_iteration(a: &Mat<f64>, x0: &Vector<f64>, tol: f64, max_iter: usize) -> EigPair
+{
+    let mut pair = EigPair { eigval: 0.0, eigvec: x0.clone() / x0.mag(), residual: f64::INFINITY, iterations: 0 };
+    _update(a, &mut pair);
+
+    for _ in 0..max_iter
+    {
+        if pair.residual <= tol
+        {
+            break;
+        }
+        let y = ShiftInvert::new(a, pair.eigval).apply(&pair.eigvec);
+        let norm = y.mag();
+        if !norm.is_finite()
+        {
+            // The shift hit an eigenvalue exactly.
+            break;
+        }
+        pair.eigvec = y / norm;
+        pair.iterations += 1;
+        _update(a, &mut pair);
+    }
+    pair
+}
+
+// Sets the eigenvalue to the Rayleigh quotient and returns the residual.
+fn _update(a: &Mat<f64>, pair: &mut EigPair) -> f64
+{
+    let y = a.vecmul(&pair.eigvec);
+    pair.eigval = pair.eigvec.dot(&y);
+    pair.residual = (y - pair.eigvec.clone() * pair.eigval).mag();
+    pair.residual
+}
@@ -0,0 +1,350 @@
+use crate::matrix::Mat;
+use crate::vector::Vector;
+use crate::decompositions::eig::EIG;
+use crate::decompositions::schur::hessenberg_qr;
+use crate::decompositions::givens::Givens;
+use crate::eigensolvers::{LinearOperator, Which, _start_vector};
+
+
+#[derive(Clone, Copy)]
+enum Order
+{
+    LargestAlgebraic,
+    SmallestAlgebraic,
+    LargestMagnitude,
+    SmallestMagnitude,
+}
+
+// Implicitly restarted Lanczos with full reorthogonalization, for symmetric
+// operators. Largest and Smallest refer to the algebraic order.
+pub fn lanczos<A>(op: &A, nev: usize, which: Which, tol: f64, max_iter: usize) -> Result<EIG<f64>, &'static str>
+where A: LinearOperator + ?Sized
+{
+    match which
+    {
+        Which::Largest => _restarted(op, nev, Order::LargestAlgebraic, tol, max_iter, true),
+        Which::Smallest => _restarted(op, nev, Order::SmallestAlgebraic, tol, max_iter, true),
+        Which::NearestTo(sigma) => _shift_invert(op, nev, sigma, tol, max_iter, true),
+    }
+}
+
+// Implicitly restarted Arnoldi for general operators. Largest and Smallest
+// refer to magnitude, and only real eigenvalues can be returned.
+pub fn arnoldi<A>(op: &A, nev: usize, which: Which, tol: f64, max_iter: usize) -> Result<EIG<f64>, &'static str>
+where A: LinearOperator + ?Sized
+{
+    match which
+    {
+        Which::Largest => _restarted(op, nev, Order::LargestMagnitude, tol, max_iter, false),
+        Which::Smallest => _restarted(op, nev, Order::SmallestMagnitude, tol, max_iter, false),
+        Which::NearestTo(sigma) => _shift_invert(op, nev, sigma, tol, max_iter, false),
+    }
+}
+
+fn _shift_invert<A>(op: &A, nev: usize, sigma: f64, tol: f64, max_iter: usize, symmetric: bool) -> Result<EIG<f64>, &'static str>
+where A: LinearOperator + ?Sized
+{
+    let inverted = op.shift_invert(sigma).ok_or("Operator does not support shift-invert.")?;
+    let mut eig = _restarted(&inverted, nev, Order::LargestMagnitude, tol, max_iter, symmetric)?;
+    for i in 0..eig.eigvals.len()
+    {
+        eig.eigvals[i] = inverted.sigma + 1.0 / eig.eigvals[i];
+    }
+    Ok(eig)
+}
+
+fn _restarted<A>(op: &A, nev: usize, order: Order, tol: f64, max_iter: usize, symmetric: bool) -> Result<EIG<f64>, &'static str>
+where A: LinearOperator + ?Sized
+{
+    let n = op.dim();
+    assert!(nev > 0 && nev <= n);
+    let ncv = std::cmp::min(n, std::cmp::max(2 * nev + 1, 20));
+    let eps23 = f64::EPSILON.powf(2.0 / 3.0);
+
+    let mut v = Vec::<Vector<f64>>::with_capacity(ncv + 1);
+    let mut h = Mat::<f64>::new((ncv, ncv));
+    let mut f = _start_vector(n, 0);
+    let mut complex = false;
+
+    for _ in 0..max_iter
+    {
+        _extend(op, &mut v, &mut h, &mut f, ncv, symmetric);
+
+        let mut t = h.clone();
+        let mut z = Mat::eye(ncv);
+        let (wr, wi) = hessenberg_qr(&mut t, &mut z)?;
+        let key = |i: usize| match order
+        {
+            Order::LargestAlgebraic => wr[i],
+            Order::SmallestAlgebraic => -wr[i],
+            Order::LargestMagnitude => wr[i].hypot(wi[i]),
+            Order::SmallestMagnitude => -wr[i].hypot(wi[i]),
+        };
+        let mut ritz = (0..ncv).collect::<Vec<usize>>();
+        ritz.sort_by(|&a, &b| key(b).total_cmp(&key(a)));
+
+        // Residual of a Ritz pair is |f| times the last component of y.
+        let beta = f.mag();
+        let mut pairs = Vec::with_capacity(nev);
+        complex = false;
+        for &i in ritz[..nev].iter()
+        {
+            if wi[i] != 0.0
+            {
+                complex = true;
+                break;
+            }
+            let y = if symmetric { _column(&z, i) } else { _ritz_vector(&h, wr[i]) };
+            if beta * y[ncv - 1].abs() > tol * wr[i].abs().max(eps23)
+            {
+                break;
+            }
+            pairs.push((wr[i], y));
+        }
+
+        if pairs.len() == nev
+        {
+            let mut eigvals = Vector::new(nev);
+            let mut eigvecs = Mat::new((n, nev));
+            for (j, (theta, y)) in pairs.into_iter().enumerate()
+            {
+                let x = _combine(&v, &y);
+                let norm = x.mag();
+                eigvals[j] = theta;
+                for i in 0..n
+                {
+                    eigvecs[(i, j)] = x[i] / norm;
+                }
+            }
+            return Ok(EIG { eigvals, eigvecs });
+        }
+
+        // Keep conjugate pairs together.
+        let mut keep = nev;
+        if keep < ncv && wi[ritz[keep - 1]] != 0.0 && wi[ritz[keep]] == -wi[ritz[keep - 1]]
+        {
+            keep += 1;
+        }
+        if keep >= ncv
+        {
+            break;
+        }
+
+        // Filter out the unwanted Ritz values, using them as exact shifts.
+        let mut q = Mat::eye(ncv);
+        for &i in ritz[keep..].iter()
+        {
+            // The conjugate of a complex shift is applied with it.
+            if wi[i] >= 0.0
+            {
+                _shift_step(&mut h, &mut q, wr[i], wi[i]);
+            }
+        }
+
+        // Truncate the factorization to the first keep columns.
+        let mut restarted = Vec::with_capacity(ncv + 1);
+        for j in 0..=keep
+        {
+            restarted.push(_combine(&v, &_column(&q, j)));
+        }
+        let sub = h[(keep, keep - 1)];
+        let last = q[(ncv - 1, keep - 1)];
+        for i in 0..n
+        {
+            f[i] = restarted[keep][i] * sub + f[i] * last;
+        }
+        restarted.truncate(keep);
+        v = restarted;
+
+        for row in 0..ncv
+        {
+            for col in 0..ncv
+            {
+                if row >= keep || col >= keep || row > col + 1
+                {
+                    h[(row, col)] = 0.0;
+                }
+            }
+        }
+        if symmetric
+        {
+            _symmetrize(&mut h, keep);
+        }
+    }
+
+    if complex
+    {
+        return Err("Requested eigenvalues are complex.");
+    }
+    Err("Did not converge.")
+}
+
+// Implicit shifted QR step h = qᵀ h q on the Hessenberg matrix h, with the
+// shift re or the conjugate pair re ± i im. The first column of the shift
+// polynomial is rotated onto e1 and the resulting bulge chased down.
+fn _shift_step(h: &mut Mat<f64>, q: &mut Mat<f64>, re: f64, im: f64)
+{
+    let m = h.rows;
+    if m < 2
+    {
+        return;
+    }
+    let (x, y, z) = if im == 0.0
+    {
+        (h[(0, 0)] - re, h[(1, 0)], 0.0)
+    }
+    else
+    {
+        let h21 = if m > 2 { h[(2, 1)] } else { 0.0 };
+        (h[(0, 0)] * h[(0, 0)] + h[(0, 1)] * h[(1, 0)] - 2.0 * re * h[(0, 0)] + re * re + im * im,
+         h[(1, 0)] * (h[(0, 0)] + h[(1, 1)] - 2.0 * re),
+         h[(1, 0)] * h21)
+    };
+    if z != 0.0
+    {
+        _rotate(h, q, Givens::new(y, z), 1, 2);
+    }
+    _rotate(h, q, Givens::new(x, y.hypot(z)), 0, 1);
+
+    for k in 0..m.saturating_sub(2)
+    {
+        for i in ((k + 2)..m).rev()
+        {
+            if h[(i, k)] != 0.0
+            {
+                _rotate(h, q, Givens::new(h[(i - 1, k)], h[(i, k)]), i - 1, i);
+                h[(i, k)] = 0.0;
+            }
+        }
+    }
+}
+
+fn _rotate(h: &mut Mat<f64>, q: &mut Mat<f64>, g: Givens, p: usize, r: usize)
+{
+    let (m, rows) = (h.rows, q.rows);
+    g.rotate_rows(h, p, r, 0);
+    g.rotate_cols(h, p, r, m);
+    g.rotate_cols(q, p, r, rows);
+}
+
+// Extends A V = V H + f eᵀ until V has ncv columns.
+fn _extend<A>(op: &A, v: &mut Vec<Vector<f64>>, h: &mut Mat<f64>, f: &mut Vector<f64>, ncv: usize, symmetric: bool)
+where A: LinearOperator + ?Sized
+{
+    let n = op.dim();
+    for j in v.len()..ncv
+    {
+        let beta = f.mag();
+        let mut hnorm = 0.0;
+        for x in h.iter()
+        {
+            hnorm += x * x;
+        }
+
+        let next = if j > 0 && beta <= 1e-12 * hnorm.sqrt()
+        {
+            // Invariant subspace found, continue with a fresh direction.
+            let mut fresh = _start_vector(n, j as u64 + 1);
+            _orthogonalize(v, &mut fresh);
+            _orthogonalize(v, &mut fresh);
+            let norm = fresh.mag();
+            h[(j, j - 1)] = 0.0;
+            fresh / norm
+        }
+        else
+        {
+            if j > 0
+            {
+                h[(j, j - 1)] = beta;
+            }
+            f.clone() / beta
+        };
+
+        let mut w = op.apply(&next);
+        v.push(next);
+        let mut coeffs = _orthogonalize(v, &mut w);
+        let correction = _orthogonalize(v, &mut w);
+        for (c, d) in coeffs.iter_mut().zip(correction)
+        {
+            *c += d;
+        }
+        for (i, c) in coeffs.into_iter().enumerate()
+        {
+            h[(i, j)] = c;
+        }
+        if symmetric
+        {
+            _symmetrize(h, j + 1);
+        }
+        *f = w;
+    }
+}
+
+// Removes the components of w along the columns of v and returns them.
+fn _orthogonalize(v: &[Vector<f64>], w: &mut Vector<f64>) -> Vec<f64>
+{
+    let coeffs = v.iter().map(|vi| vi.dot(w)).collect::<Vec<f64>>();
+    for (vi, c) in v.iter().zip(coeffs.iter())
+    {
+        for k in 0..w.len()
+        {
+            w[k] -= c * vi[k];
+        }
+    }
+    coeffs
+}
+
+// Forces the leading size x size block of h to be symmetric tridiagonal.
+fn _symmetrize(h: &mut Mat<f64>, size: usize)
+{
+    for col in 0..size
+    {
+        for row in 0..col
+        {
+            h[(row, col)] = if row + 1 == col { h[(col, row)] } else { 0.0 };
+        }
+    }
+}
+
+fn _combine(v: &[Vector<f64>], y: &Vector<f64>) -> Vector<f64>
+{
+    let mut x = Vector::new(v[0].len());
+    for (vi, c) in v.iter().zip(y.iter())
+    {
+        for k in 0..x.len()
+        {
+            x[k] += c * vi[k];
+        }
+    }
+    x
+}
+
+fn _column(mat: &Mat<f64>, col: usize) -> Vector<f64>
+{
+    let mut out = Vector::new(mat.rows);
+    for row in 0..mat.rows
+    {
+        out[row] = mat[(row, col)];
+    }
+    out
+}
+
+// Eigenvector of the small Hessenberg matrix h by inverse iteration.
+fn _ritz_vector(h: &Mat<f64>, theta: f64) -> Vector<f64>
+{
+    let m = h.rows;
+    let mut shifted = h.clone();
+    for i in 0..m
+    {
+        shifted[(i, i)] -= theta + 1e-10 * theta.abs().max(1.0);
+    }
+    let lup = shifted.lup();
+    let mut y = Vector::from_vec(vec![1.0; m]);
+    for _ in 0..3
+    {
+        y = lup.solve(&y);
+        let norm = y.mag();
+        y /= norm;
+    }
+    y
+}
@@ -0,0 +1,111 @@
+pub mod krylov;
+pub mod iteration;
+
+use crate::matrix::Mat;
+use crate::vector::Vector;
+use crate::decompositions::lup::LUP;
+
+
+pub trait LinearOperator
+{
+    fn dim(&self) -> usize;
+    fn apply(&self, x: &Vector<f64>) -> Vector<f64>;
+
+    // Operator x -> (A - sigma I)^-1 x, if the operator can be factorized.
+    fn shift_invert(&self, _sigma: f64) -> Option<ShiftInvert>
+    {
+        None
+    }
+}
+
+impl LinearOperator for Mat<f64>
+{
+    fn dim(&self) -> usize
+    {
+        assert!(self.rows == self.cols);
+        self.rows
+    }
+
+    fn apply(&self, x: &Vector<f64>) -> Vector<f64>
+    {
+        self.vecmul(x)
+    }
+
+    fn shift_invert(&self, sigma: f64) -> Option<ShiftInvert>
+    {
+        Some(ShiftInvert::new(self, sigma))
+    }
+}
+
+pub struct ShiftInvert
+{
+    pub sigma: f64,
+    lup: LUP<f64>,
+}
+
+impl ShiftInvert
+{
+    // A sigma that is an eigenvalue of a, up to rounding, is moved away by a
+    // few ulps of the norm of a so the factorization stays usable. The shift
+    // actually used is kept in sigma.
+    pub fn new(a: &Mat<f64>, sigma: f64) -> Self
+    {
+        assert!(a.rows == a.cols);
+        let n = a.rows;
+        let scale = a.iter().fold(sigma.abs(), |acc, x| acc.max(x.abs())).max(f64::MIN_POSITIVE);
+        let threshold = (n as f64) * f64::EPSILON * scale;
+        let mut delta = 1e3 * threshold;
+        let mut sigma = sigma;
+        loop
+        {
+            let mut shifted = a.clone();
+            for i in 0..n
+            {
+                shifted[(i, i)] -= sigma;
+            }
+            let lup = shifted.lup();
+            if delta > 1e-3 * scale || lup.compact.iter_trace().all(|d| d.abs() > threshold)
+            {
+                return Self { sigma, lup };
+            }
+            sigma += delta;
+            delta *= 2.0;
+        }
+    }
+}
+
+impl LinearOperator for ShiftInvert
+{
+    fn dim(&self) -> usize
+    {
+        self.lup.compact.rows
+    }
+
+    fn apply(&self, x: &Vector<f64>) -> Vector<f64>
+    {
+        self.lup.solve(x)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Which
+{
+    Largest,
+    Smallest,
+    NearestTo(f64),
+}
+
+// Deterministic pseudo random start vector.
+pub(crate) fn _start_vector(n: usize, seed: u64) -> Vector<f64>
+{
+    let mut state = 0x9E3779B97F4A7C15u64 ^ seed.wrapping_mul(0xBF58476D1CE4E5B9);
+    let mut out = Vector::new(n);
+    for i in 0..n
+    {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        out[i] = (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5;
+    }
+    out
+}
@@ -0,0 +1,2 @@
+pub mod sylvester;
+pub mod riccati;
\ No newline at end of file
@@ -0,0 +1,131 @@
+use crate::matrix::Mat;
+use crate::vector::Vector;
+
+
+// Solves aᵀ x + x a - x b r^-1 bᵀ x + q = 0 for the stabilizing x, using the
+// ordered Schur form of the Hamiltonian matrix.
+pub fn solve_continuous_are(a: &Mat<f64>, b: &Mat<f64>, q: &Mat<f64>, r: &Mat<f64>) -> Result<Mat<f64>, &'static str>
+{
+    let n = _check_shapes(a, b, q, r);
+    let g = b.matmul(&_inverse(r, "R is singular.")?).matmul(&b.transposed());
+
+    let mut hamiltonian = Mat::new((2 * n, 2 * n));
+    for i in 0..n
+    {
+        for j in 0..n
+        {
+            hamiltonian[(i, j)] = a[(i, j)];
+            hamiltonian[(i, j + n)] = -g[(i, j)];
+            hamiltonian[(i + n, j)] = -q[(i, j)];
+            hamiltonian[(i + n, j + n)] = -a[(j, i)];
+        }
+    }
+
+    let (u, _, stable) = hamiltonian.ordered_schur(|re, _| re < 0.0)?;
+    if stable != n
+    {
+        return Err("No stabilizing solution, the Hamiltonian has eigenvalues on the imaginary axis.");
+    }
+    _subspace_solution(&u, n)
+}
+
+// Solves aᵀ x a - x - aᵀ x b (r + bᵀ x b)^-1 bᵀ x a + q = 0 for the stabilizing
+// x, using the ordered generalized Schur form of the symplectic pencil.
+pub fn solve_discrete_are(a: &Mat<f64>, b: &Mat<f64>, q: &Mat<f64>, r: &Mat<f64>) -> Result<Mat<f64>, &'static str>
+{
+    let n = _check_shapes(a, b, q, r);
+    let g = b.matmul(&_inverse(r, "R is singular.")?).matmul(&b.transposed());
+
+    // The pencil is singular when a is, QZ reports the infinite eigenvalues
+    // and they are never selected.
+    let mut m = Mat::new((2 * n, 2 * n));
+    let mut l = Mat::new((2 * n, 2 * n));
+    for i in 0..n
+    {
+        for j in 0..n
+        {
+            m[(i, j)] = a[(i, j)];
+            m[(i + n, j)] = -q[(i, j)];
+            l[(i, j + n)] = g[(i, j)];
+            l[(i + n, j + n)] = a[(j, i)];
+        }
+        m[(i + n, i + n)] = 1.0;
+        l[(i, i)] = 1.0;
+    }
+
+    let mut qz = m.qz(l)?;
+    let stable = qz.reorder(|re, im| re * re + im * im < 1.0);
+    if stable != n
+    {
+        return Err("No stabilizing solution, the pencil has eigenvalues on the unit circle.");
+    }
+    _subspace_solution(&qz.z, n)
+}
+
+// Gain k of the state feedback u = -k x minimizing ∫ xᵀ q x + uᵀ r u.
+pub fn lqr(a: &Mat<f64>, b: &Mat<f64>, q: &Mat<f64>, r: &Mat<f64>) -> Result<Mat<f64>, &'static str>
+{
+    let x = solve_continuous_are(a, b, q, r)?;
+    Ok(_inverse(r, "R is singular.")?.matmul(&b.transposed()).matmul(&x))
+}
+
+// Discrete time counterpart of lqr.
+pub fn dlqr(a: &Mat<f64>, b: &Mat<f64>, q: &Mat<f64>, r: &Mat<f64>) -> Result<Mat<f64>, &'static str>
+{
+    let x = solve_discrete_are(a, b, q, r)?;
+    let bt = b.transposed();
+    let gram = r.clone() + bt.matmul(&x).matmul(b);
+    Ok(_inverse(&gram, "R + Bᵀ X B is singular.")?.matmul(&bt).matmul(&x).matmul(a))
+}
+
+fn _check_shapes(a: &Mat<f64>, b: &Mat<f64>, q: &Mat<f64>, r: &Mat<f64>) -> usize
+{
+    let n = a.rows;
+    assert!(a.cols == n && b.rows == n && q.shape() == (n, n));
+    assert!(r.shape() == (b.cols, b.cols));
+    n
+}
+
+// Inverse through LUP, with err when a pivot is negligible against the
+// largest entry of the factorization.
+fn _inverse(m: &Mat<f64>, err: &'static str) -> Result<Mat<f64>, &'static str>
+{
+    let lup = m.clone().lup();
+    let scale = lup.compact.iter().fold(0.0f64, |acc, x| acc.max(x.abs()));
+    if lup.compact.iter_trace().any(|d| !d.is_finite() || d.abs() <= 1e3 * f64::EPSILON * scale)
+    {
+        return Err(err);
+    }
+    Ok(lup.inv())
+}
+
+// x = u21 u11^-1 from the leading n columns of u.
+fn _subspace_solution(u: &Mat<f64>, n: usize) -> Result<Mat<f64>, &'static str>
+{
+    let u11 = u.submat(0, n, 0, n).unwrap();
+    let u21 = u.submat(n, 2 * n, 0, n).unwrap();
+    let lup = u11.transposed().lup();
+    let scale = lup.compact.iter().fold(0.0f64, |acc, x| acc.max(x.abs()));
+    if lup.compact.iter_trace().any(|d| d.abs() <= 1e3 * f64::EPSILON * scale)
+    {
+        return Err("No stabilizing solution, the stable subspace is not a graph.");
+    }
+
+    // Solve u11ᵀ xᵀ = u21ᵀ and symmetrize the result.
+    let mut x = Mat::new((n, n));
+    let u21t = u21.transposed();
+    let mut rhs = Vector::new(n);
+    for col in 0..n
+    {
+        for row in 0..n
+        {
+            rhs[row] = u21t[(row, col)];
+        }
+        let sol = lup.solve(&rhs);
+        for row in 0..n
+        {
+            x[(col, row)] = sol[row];
+        }
+    }
+    Ok((x.clone() + x.transposed()) * 0.5)
+}
@@ -0,0 +1,199 @@
+use crate::matrix::Mat;
+use crate::vector::Vector;
+
+
+// Solves a x + x b = c by the Bartels–Stewart algorithm.
+pub fn solve_sylvester(a: &Mat<f64>, b: &Mat<f64>, c: &Mat<f64>) -> Result<Mat<f64>, &'static str>
+{
+    assert!(a.rows == a.cols && b.rows == b.cols);
+    assert!(c.rows == a.rows && c.cols == b.rows);
+
+    let (u, ta) = a.clone().schur()?;
+    let (v, tb) = b.clone().schur()?;
+    let f = u.transposed().matmul(c).matmul(&v);
+    let scale = _max_abs(&ta).max(_max_abs(&tb));
+
+    // Solve ta y + y tb = f block column by block column, bottom up.
+    let (m, n) = f.shape();
+    let row_blocks = _blocks(&ta);
+    let mut y = Mat::<f64>::new((m, n));
+    for &(j, q) in _blocks(&tb).iter()
+    {
+        for &(i, p) in row_blocks.iter().rev()
+        {
+            let mut rhs = Vec::with_capacity(p * q);
+            for col in j..(j + q)
+            {
+                for row in i..(i + p)
+                {
+                    let mut r = f[(row, col)];
+                    for k in (i + p)..m
+                    {
+                        r -= ta[(row, k)] * y[(k, col)];
+                    }
+                    for k in 0..j
+                    {
+                        r -= y[(row, k)] * tb[(k, col)];
+                    }
+                    rhs.push(r);
+                }
+            }
+
+            // (I ⊗ ta_ii + tb_jjᵀ ⊗ I) vec(y_ij) = vec(rhs)
+            let mut kron = Mat::new((p * q, p * q));
+            for cq in 0..q
+            {
+                for rp in 0..p
+                {
+                    for k in 0..p
+                    {
+                        kron[(cq * p + rp, cq * p + k)] += ta[(i + rp, i + k)];
+                    }
+                    for k in 0..q
+                    {
+                        kron[(cq * p + rp, k * p + rp)] += tb[(j + k, j + cq)];
+                    }
+                }
+            }
+            let sol = _solve_small(kron, rhs, scale)
+                .ok_or("Equation is singular, a and -b share an eigenvalue.")?;
+            for cq in 0..q
+            {
+                for rp in 0..p
+                {
+                    y[(i + rp, j + cq)] = sol[cq * p + rp];
+                }
+            }
+        }
+    }
+    Ok(u.matmul(&y).matmul(&v.transposed()))
+}
+
+// Solves a x + x aᵀ = q.
+pub fn solve_continuous_lyapunov(a: &Mat<f64>, q: &Mat<f64>) -> Result<Mat<f64>, &'static str>
+{
+    solve_sylvester(a, &a.transposed(), q)
+        .map_err(|_| "Equation is singular, a has eigenvalues with λi + λj = 0.")
+}
+
+// Solves a x aᵀ - x + q = 0.
+pub fn solve_discrete_lyapunov(a: &Mat<f64>, q: &Mat<f64>) -> Result<Mat<f64>, &'static str>
+{
+    assert!(a.rows == a.cols && q.shape() == a.shape());
+    let (u, t) = a.clone().schur()?;
+    let g = u.transposed().matmul(q).matmul(&u) * -1.0;
+    let scale = _max_abs(&t).max(1.0);
+    let n = a.rows;
+    let blocks = _blocks(&t);
+
+    // t y tᵀ - y = g, row blocks bottom up, keeping w = y tᵀ for finished rows.
+    let mut y = Mat::<f64>::new((n, n));
+    let mut w = Mat::<f64>::new((n, n));
+    for &(i, p) in blocks.iter().rev()
+    {
+        for &(j, q) in blocks.iter().rev()
+        {
+            let mut rhs = Vec::with_capacity(p * q);
+            for col in j..(j + q)
+            {
+                for row in i..(i + p)
+                {
+                    let mut r = g[(row, col)];
+                    for k in (i + p)..n
+                    {
+                        r -= t[(row, k)] * w[(k, col)];
+                    }
+                    for k in i..(i + p)
+                    {
+                        let mut known = 0.0;
+                        for l in (j + q)..n
+                        {
+                            known += y[(k, l)] * t[(col, l)];
+                        }
+                        r -= t[(row, k)] * known;
+                    }
+                    rhs.push(r);
+                }
+            }
+
+            // (t_jj ⊗ t_ii - I) vec(y_ij) = vec(rhs)
+            let mut kron = Mat::new((p * q, p * q));
+            for cq in 0..q
+            {
+                for rp in 0..p
+                {
+                    for k in 0..q
+                    {
+                        for l in 0..p
+                        {
+                            kron[(cq * p + rp, k * p + l)] = t[(j + cq, j + k)] * t[(i + rp, i + l)];
+                        }
+                    }
+                    kron[(cq * p + rp, cq * p + rp)] -= 1.0;
+                }
+            }
+            let sol = _solve_small(kron, rhs, scale * scale)
+                .ok_or("Equation is singular, a has eigenvalues with λi λj = 1.")?;
+            for cq in 0..q
+            {
+                for rp in 0..p
+                {
+                    y[(i + rp, j + cq)] = sol[cq * p + rp];
+                }
+            }
+        }
+
+        for row in i..(i + p)
+        {
+            for col in 0..n
+            {
+                let mut sum = 0.0;
+                for l in col.saturating_sub(1)..n
+                {
+                    sum += y[(row, l)] * t[(col, l)];
+                }
+                w[(row, col)] = sum;
+            }
+        }
+    }
+    Ok(u.matmul(&y).matmul(&u.transposed()))
+}
+
+// Diagonal blocks of a quasi upper triangular matrix as (start, size).
+pub(crate) fn _blocks(t: &Mat<f64>) -> Vec<(usize, usize)>
+{
+    let mut blocks = Vec::new();
+    let mut i = 0;
+    while i < t.rows
+    {
+        if i + 1 < t.rows && t[(i + 1, i)] != 0.0
+        {
+            blocks.push((i, 2));
+            i += 2;
+        }
+        else
+        {
+            blocks.push((i, 1));
+            i += 1;
+        }
+    }
+    blocks
+}
+
+fn _max_abs(mat: &Mat<f64>) -> f64
+{
+    mat.iter().fold(0.0, |acc, x| acc.max(x.abs()))
+}
+
+// Solves a small dense system, None if it is numerically singular.
+fn _solve_small(kron: Mat<f64>, rhs: Vec<f64>, scale: f64) -> Option<Vec<f64>>
+{
+    let lup = kron.lup();
+    let tiny = 1e3 * f64::EPSILON * scale.max(f64::MIN_POSITIVE);
+    if lup.compact.iter_trace().any(|d| d.abs() <= tiny)
+    {
+        return None;
+    }
+    let x = lup.solve(&Vector::from_vec(rhs));
+    Some(x.iter().copied().collect())
+}
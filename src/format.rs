@@ -0,0 +1,139 @@
+use std::fmt;
+
+use crate::matrix::Mat;
+use crate::vector::Vector;
+
+
+// Dimensions longer than MAX_ITEMS only show EDGE_ITEMS entries at each end.
+const MAX_ITEMS: usize = 10;
+const EDGE_ITEMS: usize = 3;
+
+// Indices to print along a dimension, None marks the elided middle.
+fn _shown(n: usize) -> Vec<Option<usize>>
+{
+    if n <= MAX_ITEMS
+    {
+        return (0..n).map(Some).collect();
+    }
+    (0..EDGE_ITEMS).map(Some)
+        .chain(std::iter::once(None))
+        .chain((n - EDGE_ITEMS..n).map(Some))
+        .collect()
+}
+
+fn _vector(f: &mut fmt::Formatter<'_>, len: usize, entry: impl Fn(usize) -> String) -> fmt::Result
+{
+    let entries = _shown(len).into_iter()
+        .map(|i| i.map_or("...".to_string(), &entry))
+        .collect::<Vec<String>>();
+    write!(f, "[{}]", entries.join(", "))
+}
+
+// Rows on separate lines with every column right aligned to its widest entry.
+fn _grid(f: &mut fmt::Formatter<'_>, shape: (usize, usize), entry: impl Fn(usize, usize) -> String) -> fmt::Result
+{
+    if shape.0 == 0 || shape.1 == 0
+    {
+        return write!(f, "[]");
+    }
+    let (rows, cols) = (_shown(shape.0), _shown(shape.1));
+    let cells = rows.iter()
+        .map(|r| cols.iter().map(|c| match (r, c)
+        {
+            (Some(r), Some(c)) => entry(*r, *c),
+            _ => "...".to_string(),
+        }).collect::<Vec<String>>())
+        .collect::<Vec<Vec<String>>>();
+    let widths = (0..cols.len())
+        .map(|c| cells.iter().map(|row| row[c].chars().count()).max().unwrap_or(0))
+        .collect::<Vec<usize>>();
+
+    for (i, (r, row)) in rows.iter().zip(cells.iter()).enumerate()
+    {
+        write!(f, "{}", if i == 0 { "[[" } else { " [" })?;
+        if r.is_none()
+        {
+            // A single ellipsis stands for the elided rows.
+            write!(f, "...")?;
+        }
+        else
+        {
+            for (c, cell) in row.iter().enumerate()
+            {
+                let sep = if c + 1 < row.len() { ", " } else { "" };
+                write!(f, "{:>width$}{}", cell, sep, width = widths[c])?;
+            }
+        }
+        if i + 1 < rows.len()
+        {
+            writeln!(f, "],")?;
+        }
+    }
+    write!(f, "]]")
+}
+
+// Precision and sign flag of the caller, applied to every entry.
+type Spec = (Option<usize>, bool);
+
+fn _spec(f: &fmt::Formatter<'_>) -> Spec
+{
+    (f.precision(), f.sign_plus())
+}
+
+fn _display<T: fmt::Display>(spec: Spec, x: &T) -> String
+{
+    match spec
+    {
+        (Some(p), false) => format!("{:.*}", p, x),
+        (Some(p), true) => format!("{:+.*}", p, x),
+        (None, false) => format!("{}", x),
+        (None, true) => format!("{:+}", x),
+    }
+}
+
+fn _lower_exp<T: fmt::LowerExp>(spec: Spec, x: &T) -> String
+{
+    match spec
+    {
+        (Some(p), false) => format!("{:.*e}", p, x),
+        (Some(p), true) => format!("{:+.*e}", p, x),
+        (None, false) => format!("{:e}", x),
+        (None, true) => format!("{:+e}", x),
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Mat<T>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let spec = _spec(f);
+        _grid(f, (self.rows, self.cols), |r, c| _display(spec, &self[(r, c)]))
+    }
+}
+
+impl<T: fmt::LowerExp> fmt::LowerExp for Mat<T>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let spec = _spec(f);
+        _grid(f, (self.rows, self.cols), |r, c| _lower_exp(spec, &self[(r, c)]))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Vector<T>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let spec = _spec(f);
+        _vector(f, self.len(), |i| _display(spec, &self[i]))
+    }
+}
+
+impl<T: fmt::LowerExp> fmt::LowerExp for Vector<T>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let spec = _spec(f);
+        _vector(f, self.len(), |i| _lower_exp(spec, &self[i]))
+    }
+}
@@ -0,0 +1,241 @@
+use crate::matrix::Mat;
+use crate::decompositions::schur::hessenberg_qr;
+
+
+const PADE13: [f64; 14] = [
+    64764752532480000.0, 32382376266240000.0, 7771770303897600.0, 1187353796428800.0,
+    129060195264000.0, 10559470521600.0, 670442572800.0, 33522128640.0,
+    1323241920.0, 40840800.0, 960960.0, 16380.0, 182.0, 1.0,
+];
+const THETA13: f64 = 5.371920351148152;
+
+
+impl Mat<f64>
+{
+    pub fn expm(&self) -> Mat<f64>
+    {
+        assert!(self.rows == self.cols);
+        let n = self.rows;
+
+        // Scale so that the Padé approximant is accurate, then square back up.
+        let norm = _norm1(self);
+        let s = if norm > THETA13 { (norm / THETA13).log2().ceil() as i32 } else { 0 };
+        let a = self.clone() / 2f64.powi(s);
+
+        let b = &PADE13;
+        let eye = Mat::eye(n);
+        let a2 = a.matmul(&a);
+        let a4 = a2.matmul(&a2);
+        let a6 = a4.matmul(&a2);
+
+        let u_inner = a6.matmul(&_lincomb(&[(b[13], &a6), (b[11], &a4), (b[9], &a2)]))
+            + _lincomb(&[(b[7], &a6), (b[5], &a4), (b[3], &a2), (b[1], &eye)]);
+        let u = a.matmul(&u_inner);
+        let v = a6.matmul(&_lincomb(&[(b[12], &a6), (b[10], &a4), (b[8], &a2)]))
+            + _lincomb(&[(b[6], &a6), (b[4], &a4), (b[2], &a2), (b[0], &eye)]);
+
+        let p = v.clone() + u.clone();
+        let q = v - u;
+        let mut r = q.lup().inv().matmul(&p);
+
+        for _ in 0..s
+        {
+            r = r.matmul(&r);
+        }
+        r
+    }
+
+    // Principal square root, defined when no eigenvalue lies on the closed
+    // negative real axis.
+    pub fn sqrtm(&self) -> Result<Mat<f64>, &'static str>
+    {
+        assert!(self.rows == self.cols);
+        _check_principal(self)?;
+        _denman_beavers(self)
+    }
+
+    // Principal logarithm, with the same domain as sqrtm.
+    pub fn logm(&self) -> Result<Mat<f64>, &'static str>
+    {
+        // Inverse scaling and squaring: take square roots until close to I.
+        assert!(self.rows == self.cols);
+        _check_principal(self)?;
+        let n = self.rows;
+        let eye = Mat::eye(n);
+        let mut a = self.clone();
+        let mut k = 0;
+        while _norm1(&(a.clone() - eye.clone())) > 0.25
+        {
+            if k == 64
+            {
+                return Err("Square roots did not approach the identity.");
+            }
+            a = _denman_beavers(&a)?;
+            k += 1;
+        }
+
+        // log(I + X) = ∫ X (I + tX)^-1 dt over [0, 1], by Gauss–Legendre quadrature.
+        let x = a - eye.clone();
+        let (nodes, weights) = _gauss_legendre(8);
+        let mut log = Mat::new((n, n));
+        for (t, w) in nodes.into_iter().zip(weights)
+        {
+            let inv = (eye.clone() + x.clone() * t).lup().inv();
+            log += x.matmul(&inv) * w;
+        }
+        Ok(log * 2f64.powi(k))
+    }
+
+    // Negative powers of a singular matrix are an error.
+    pub fn powm(&self, p: i32) -> Result<Mat<f64>, &'static str>
+    {
+        assert!(self.rows == self.cols);
+        let mut base = self.clone();
+        if p < 0
+        {
+            let lup = base.lup();
+            if lup.compact.iter_trace().any(|d| *d == 0.0 || !d.is_finite())
+            {
+                return Err("Matrix is singular.");
+            }
+            base = lup.inv();
+        }
+        let mut e = p.unsigned_abs();
+        let mut out = Mat::eye(self.rows);
+        while e > 0
+        {
+            if e & 1 == 1
+            {
+                out = out.matmul(&base);
+            }
+            base = base.matmul(&base);
+            e >>= 1;
+        }
+        Ok(out)
+    }
+
+    pub fn powmf(&self, p: f64) -> Result<Mat<f64>, &'static str>
+    {
+        if p.fract() == 0.0 && p.abs() <= i32::MAX as f64
+        {
+            return self.powm(p as i32);
+        }
+        Ok((self.logm()? * p).expm())
+    }
+}
+
+fn _check_principal(a: &Mat<f64>) -> Result<(), &'static str>
+{
+    let (mut z, mut h) = a.clone().hessenberg();
+    let (wr, wi) = hessenberg_qr(&mut h, &mut z)?;
+    let tol = 1e-12 * _norm1(a);
+    for (re, im) in wr.iter().zip(wi.iter())
+    {
+        if im.abs() <= tol && *re <= tol
+        {
+            return Err("Matrix has an eigenvalue on the closed negative real axis.");
+        }
+    }
+    Ok(())
+}
+
+// Denman–Beavers iteration with determinant scaling, y and z are factored
+// once per step for both their inverses and determinants.
+fn _denman_beavers(a: &Mat<f64>) -> Result<Mat<f64>, &'static str>
+{
+    let n = a.rows;
+    let mut y = a.clone();
+    let mut z = Mat::eye(n);
+
+    for _ in 0..100
+    {
+        let y_lup = y.clone().lup();
+        let z_lup = z.clone().lup();
+        let det = y_lup.det() * z_lup.det();
+        if det == 0.0 || !det.is_finite()
+        {
+            return Err("Matrix is singular.");
+        }
+        let gamma = det.abs().powf(-1.0 / (2.0 * n as f64));
+
+        let y_next = (y.clone() * gamma + z_lup.inv() / gamma) * 0.5;
+        let z_next = (z * gamma + y_lup.inv() / gamma) * 0.5;
+
+        let change = _norm1(&(y_next.clone() - y));
+        y = y_next;
+        z = z_next;
+        if !change.is_finite()
+        {
+            return Err("Matrix is singular.");
+        }
+        if change <= 1e-14 * _norm1(&y)
+        {
+            return Ok(y);
+        }
+    }
+    Err("Square root iteration did not converge.")
+}
+
+fn _norm1(mat: &Mat<f64>) -> f64
+{
+    let mut norm = 0.0f64;
+    for col in 0..mat.cols
+    {
+        let mut sum = 0.0;
+        for row in 0..mat.rows
+        {
+            sum += mat[(row, col)].abs();
+        }
+        norm = norm.max(sum);
+    }
+    norm
+}
+
+fn _lincomb(terms: &[(f64, &Mat<f64>)]) -> Mat<f64>
+{
+    let mut out = Mat::new(terms[0].1.shape());
+    for (coeff, mat) in terms.iter()
+    {
+        for row in 0..out.rows
+        {
+            for col in 0..out.cols
+            {
+                out[(row, col)] += coeff * mat[(row, col)];
+            }
+        }
+    }
+    out
+}
+
+// Nodes and weights on [0, 1].
+fn _gauss_legendre(m: usize) -> (Vec<f64>, Vec<f64>)
+{
+    let mut nodes = vec![0.0; m];
+    let mut weights = vec![0.0; m];
+    for i in 0..m
+    {
+        let mut x = (std::f64::consts::PI * (i as f64 + 0.75) / (m as f64 + 0.5)).cos();
+        let mut dp = 0.0;
+        for _ in 0..100
+        {
+            // Evaluate P_m(x) and its derivative by the three-term recurrence.
+            let (mut p0, mut p1) = (1.0, x);
+            for k in 2..=m
+            {
+                let p2 = ((2 * k - 1) as f64 * x * p1 - (k - 1) as f64 * p0) / k as f64;
+                p0 = p1;
+                p1 = p2;
+            }
+            dp = m as f64 * (x * p1 - p0) / (x * x - 1.0);
+            let dx = p1 / dp;
+            x -= dx;
+            if dx.abs() < 1e-15
+            {
+                break;
+            }
+        }
+        nodes[i] = 0.5 * (1.0 - x);
+        weights[i] = 1.0 / ((1.0 - x * x) * dp * dp);
+    }
+    (nodes, weights)
+}
@@ -0,0 +1,663 @@
+use std::io::{Read, Write};
+use std::ops::Index;
+use std::path::Path;
+
+use crate::matrix::Mat;
+use crate::vector::Vector;
+use crate::sparse::Coo;
+use crate::io::{ReadError, _crc32};
+use crate::io::npy::Element;
+use crate::decompositions::lu::LU;
+use crate::decompositions::lup::LUP;
+use crate::decompositions::qr::QR;
+use crate::decompositions::eig::EIG;
+use crate::decompositions::cholesky::Cholesky;
+use crate::decompositions::ldl::LDL;
+
+
+// Layout, all integers little endian and every block aligned to 64 bytes:
+//
+//   file header   magic (8), version u16, kind u16, array count u32, padding
+//   array header  dtype u8, layout u8, ndim u8, 0u8, crc32 of data u32,
+//                 shape u64 x 2, data length u64, padding
+//   array data    elements in the given layout, padding
+const MAGIC: &[u8; 8] = b"REDPILL\0";
+const VERSION: u16 = 1;
+const BLOCK: usize = 64;
+
+const ROW_MAJOR: u8 = 0;
+const COL_MAJOR: u8 = 1;
+
+// Objects that can be saved as a sequence of arrays.
+pub trait Persist: Sized
+{
+    const KIND: u16;
+
+    fn arrays(&self) -> Vec<Array>;
+    fn from_arrays(arrays: Vec<Array>) -> Result<Self, ReadError>;
+}
+
+// One block of the file with its elements as little endian bytes.
+pub struct Array
+{
+    dtype: u8,
+    layout: u8,
+    shape: Vec<usize>,
+    bytes: Vec<u8>,
+}
+
+fn _dtype<T: Element>() -> u8
+{
+    match (T::KIND, T::SIZE)
+    {
+        ('f', 4) => 1,
+        ('f', 8) => 2,
+        ('i', 8) => 3,
+        ('u', 8) => 4,
+        _ => unreachable!(),
+    }
+}
+
+fn _bytes<T: Element>(elements: &[T]) -> Vec<u8>
+{
+    let mut bytes = Vec::with_capacity(elements.len() * T::SIZE);
+    for &e in elements
+    {
+        e.to_le(&mut bytes);
+    }
+    bytes
+}
+
+impl Array
+{
+    fn from_mat<T: Element>(mat: &Mat<T>) -> Array
+    {
+        let (layout, bytes) = if mat._is_row_major()
+        {
+            (ROW_MAJOR, _bytes(mat._elements()))
+        }
+        else if mat._is_col_major()
+        {
+            (COL_MAJOR, _bytes(mat._elements()))
+        }
+        else
+        {
+            let mut bytes = Vec::with_capacity(mat.rows * mat.cols * T::SIZE);
+            for r in 0..mat.rows
+            {
+                for c in 0..mat.cols
+                {
+                    mat[(r, c)].to_le(&mut bytes);
+                }
+            }
+            (ROW_MAJOR, bytes)
+        };
+        Array { dtype: _dtype::<T>(), layout, shape: vec![mat.rows, mat.cols], bytes }
+    }
+
+    fn from_slice<T: Element>(elements: &[T]) -> Array
+    {
+        Array { dtype: _dtype::<T>(), layout: ROW_MAJOR, shape: vec![elements.len()], bytes: _bytes(elements) }
+    }
+
+    fn from_indices(indices: &[usize]) -> Array
+    {
+        Array::from_slice(&indices.iter().map(|&i| i as u64).collect::<Vec<u64>>())
+    }
+
+    fn _check<T: Element>(&self, ndim: usize) -> Result<(), ReadError>
+    {
+        if self.dtype != _dtype::<T>()
+        {
+            return Err(ReadError::Format(format!("Expected dtype {}, found {}.", _dtype::<T>(), self.dtype)));
+        }
+        if self.shape.len() != ndim
+        {
+            return Err(ReadError::Format(format!("Expected a {}-d array, found {}-d.", ndim, self.shape.len())));
+        }
+        Ok(())
+    }
+
+    fn into_mat<T: Element>(self) -> Result<Mat<T>, ReadError>
+    {
+        self._check::<T>(2)?;
+        let (rows, cols) = (self.shape[0], self.shape[1]);
+        let elements = self.bytes.chunks_exact(T::SIZE).map(|b| T::from_bytes(b, true)).collect::<Vec<T>>();
+        Ok(match self.layout
+        {
+            COL_MAJOR => Mat::_from_strided((rows, cols), 1, rows, elements),
+            _ => Mat::_from_strided((rows, cols), cols, 1, elements),
+        })
+    }
+
+    fn into_vec<T: Element>(self) -> Result<Vec<T>, ReadError>
+    {
+        self._check::<T>(1)?;
+        Ok(self.bytes.chunks_exact(T::SIZE).map(|b| T::from_bytes(b, true)).collect())
+    }
+
+    fn into_indices(self) -> Result<Vec<usize>, ReadError>
+    {
+        Ok(self.into_vec::<u64>()?.into_iter().map(|i| i as usize).collect())
+    }
+}
+
+pub fn save<P: Persist, W: Write>(mut writer: W, object: &P) -> std::io::Result<()>
+{
+    let arrays = object.arrays();
+    let mut header = [0u8; BLOCK];
+    header[..8].copy_from_slice(MAGIC);
+    header[8..10].copy_from_slice(&VERSION.to_le_bytes());
+    header[10..12].copy_from_slice(&P::KIND.to_le_bytes());
+    header[12..16].copy_from_slice(&(arrays.len() as u32).to_le_bytes());
+    writer.write_all(&header)?;
+
+    for array in arrays.iter()
+    {
+        let mut header = [0u8; BLOCK];
+        header[0] = array.dtype;
+        header[1] = array.layout;
+        header[2] = array.shape.len() as u8;
+        header[4..8].copy_from_slice(&_crc32(&array.bytes).to_le_bytes());
+        for (i, &s) in array.shape.iter().enumerate()
+        {
+            header[8 + 8 * i..16 + 8 * i].copy_from_slice(&(s as u64).to_le_bytes());
+        }
+        header[24..32].copy_from_slice(&(array.bytes.len() as u64).to_le_bytes());
+        writer.write_all(&header)?;
+        writer.write_all(&array.bytes)?;
+        writer.write_all(&[0u8; BLOCK][..array.bytes.len().next_multiple_of(BLOCK) - array.bytes.len()])?;
+    }
+    Ok(())
+}
+
+pub fn load<P: Persist, R: Read>(mut reader: R) -> Result<P, ReadError>
+{
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    let (kind, blocks) = _parse(&bytes)?;
+    _check_kind::<P>(kind)?;
+    let arrays = blocks.into_iter()
+        .map(|b| Array { dtype: b.dtype, layout: b.layout, shape: b.shape, bytes: bytes[b.start..b.end].to_vec() })
+        .collect();
+    P::from_arrays(arrays)
+}
+
+pub fn save_file<P: Persist>(path: impl AsRef<Path>, object: &P) -> std::io::Result<()>
+{
+    let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+    save(&mut writer, object)?;
+    writer.flush()
+}
+
+pub fn load_file<P: Persist>(path: impl AsRef<Path>) -> Result<P, ReadError>
+{
+    load(std::fs::File::open(path)?)
+}
+
+fn _check_kind<P: Persist>(kind: u16) -> Result<(), ReadError>
+{
+    if kind != P::KIND
+    {
+        return Err(ReadError::Format(format!("Expected object kind {}, found {}.", P::KIND, kind)));
+    }
+    Ok(())
+}
+
+struct Block
+{
+    dtype: u8,
+    layout: u8,
+    shape: Vec<usize>,
+    start: usize,
+    end: usize,
+}
+
+// Validates the headers and checksums and locates the data of every array.
+fn _parse(bytes: &[u8]) -> Result<(u16, Vec<Block>), ReadError>
+{
+    let truncated = || ReadError::Format("Truncated file.".to_string());
+    let u16_at = |at: usize| u16::from_le_bytes([bytes[at], bytes[at + 1]]);
+    let u32_at = |at: usize| u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap());
+    let u64_at = |at: usize| u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap()) as usize;
+
+    if bytes.len() < BLOCK || &bytes[..8] != MAGIC
+    {
+        return Err(ReadError::Format("Not a redpill binary file.".to_string()));
+    }
+    if u16_at(8) != VERSION
+    {
+        return Err(ReadError::Format(format!("Unsupported version {}.", u16_at(8))));
+    }
+    let kind = u16_at(10);
+    let count = u32_at(12) as usize;
+    // Every array takes at least one header block.
+    if count > (bytes.len() - BLOCK) / BLOCK
+    {
+        return Err(truncated());
+    }
+
+    let mut blocks = Vec::with_capacity(count);
+    let mut at = BLOCK;
+    for _ in 0..count
+    {
+        if at + BLOCK > bytes.len()
+        {
+            return Err(truncated());
+        }
+        let (dtype, layout, ndim) = (bytes[at], bytes[at + 1], bytes[at + 2] as usize);
+        let size = match dtype { 1 => 4, 2..=4 => 8, _ => return Err(ReadError::Format(format!("Unknown dtype {}.", dtype))) };
+        if !(1..=2).contains(&ndim) || layout > COL_MAJOR
+        {
+            return Err(ReadError::Format("Invalid array header.".to_string()));
+        }
+        let shape = (0..ndim).map(|i| u64_at(at + 8 + 8 * i)).collect::<Vec<usize>>();
+        let len = u64_at(at + 24);
+        let count = shape.iter().try_fold(1usize, |acc, &s| acc.checked_mul(s));
+        if count.and_then(|c| c.checked_mul(size)) != Some(len)
+        {
+            return Err(ReadError::Format(format!("Shape {:?} does not match {} bytes of data.", shape, len)));
+        }
+
+        let start = at + BLOCK;
+        let end = start.checked_add(len).ok_or_else(truncated)?;
+        if end > bytes.len()
+        {
+            return Err(truncated());
+        }
+        if _crc32(&bytes[start..end]) != u32_at(at + 4)
+        {
+            return Err(ReadError::Format(format!("Checksum mismatch in array {}.", blocks.len())));
+        }
+        blocks.push(Block { dtype, layout, shape, start, end });
+        at = start + len.next_multiple_of(BLOCK);
+    }
+    Ok((kind, blocks))
+}
+
+fn _expect(arrays: Vec<Array>, count: usize) -> Result<std::vec::IntoIter<Array>, ReadError>
+{
+    if arrays.len() != count
+    {
+        return Err(ReadError::Format(format!("Expected {} arrays, found {}.", count, arrays.len())));
+    }
+    Ok(arrays.into_iter())
+}
+
+impl<T: Element> Persist for Mat<T>
+{
+    const KIND: u16 = 1;
+
+    fn arrays(&self) -> Vec<Array>
+    {
+        vec![Array::from_mat(self)]
+    }
+
+    fn from_arrays(arrays: Vec<Array>) -> Result<Self, ReadError>
+    {
+        _expect(arrays, 1)?.next().unwrap().into_mat()
+    }
+}
+
+impl<T: Element> Persist for Vector<T>
+{
+    const KIND: u16 = 2;
+
+    fn arrays(&self) -> Vec<Array>
+    {
+        vec![Array::from_slice(&self.iter().copied().collect::<Vec<T>>())]
+    }
+
+    fn from_arrays(arrays: Vec<Array>) -> Result<Self, ReadError>
+    {
+        Ok(Vector::from_vec(_expect(arrays, 1)?.next().unwrap().into_vec()?))
+    }
+}
+
+impl Persist for Coo<f64>
+{
+    const KIND: u16 = 3;
+
+    fn arrays(&self) -> Vec<Array>
+    {
+        vec![
+            Array::from_indices(&[self.rows, self.cols]),
+            Array::from_indices(&self.entries.iter().map(|e| e.0).collect::<Vec<usize>>()),
+            Array::from_indices(&self.entries.iter().map(|e| e.1).collect::<Vec<usize>>()),
+            Array::from_slice(&self.entries.iter().map(|e| e.2).collect::<Vec<f64>>()),
+        ]
+    }
+
+    fn from_arrays(arrays: Vec<Array>) -> Result<Self, ReadError>
+    {
+        let mut arrays = _expect(arrays, 4)?;
+        let shape = arrays.next().unwrap().into_indices()?;
+        let rows = arrays.next().unwrap().into_indices()?;
+        let cols = arrays.next().unwrap().into_indices()?;
+        let values = arrays.next().unwrap().into_vec::<f64>()?;
+        if shape.len() != 2 || rows.len() != values.len() || cols.len() != values.len()
+        {
+            return Err(ReadError::Format("Inconsistent sparse arrays.".to_string()));
+        }
+        let mut coo = Coo::new((shape[0], shape[1]));
+        for ((r, c), v) in rows.into_iter().zip(cols).zip(values)
+        {
+            if r >= coo.rows || c >= coo.cols
+            {
+                return Err(ReadError::Format(format!("Entry ({}, {}) out of bounds.", r, c)));
+            }
+            coo.push(r, c, v);
+        }
+        Ok(coo)
+    }
+}
+
+fn _square(mat: Mat<f64>, n: Option<usize>) -> Result<Mat<f64>, ReadError>
+{
+    if mat.rows != mat.cols || n.is_some_and(|n| n != mat.rows)
+    {
+        return Err(ReadError::Format(format!("Unexpected factor shape ({}, {}).", mat.rows, mat.cols)));
+    }
+    Ok(mat)
+}
+
+fn _perm(perm: Vec<usize>, n: usize) -> Result<Vec<usize>, ReadError>
+{
+    let mut seen = vec![false; n];
+    if perm.len() != n || perm.iter().any(|&p| p >= n || std::mem::replace(&mut seen[p], true))
+    {
+        return Err(ReadError::Format("Invalid permutation.".to_string()));
+    }
+    Ok(perm)
+}
+
+impl Persist for LU<f64>
+{
+    const KIND: u16 = 4;
+
+    fn arrays(&self) -> Vec<Array>
+    {
+        vec![Array::from_mat(&self.compact)]
+    }
+
+    fn from_arrays(arrays: Vec<Array>) -> Result<Self, ReadError>
+    {
+        Ok(LU { compact: _square(_expect(arrays, 1)?.next().unwrap().into_mat()?, None)? })
+    }
+}
+
+impl Persist for LUP<f64>
+{
+    const KIND: u16 = 5;
+
+    fn arrays(&self) -> Vec<Array>
+    {
+        vec![Array::from_mat(&self.compact), Array::from_indices(&self.perm), Array::from_indices(&[self.num_swaps])]
+    }
+
+    fn from_arrays(arrays: Vec<Array>) -> Result<Self, ReadError>
+    {
+        let mut arrays = _expect(arrays, 3)?;
+        let compact = _square(arrays.next().unwrap().into_mat()?, None)?;
+        let perm = _perm(arrays.next().unwrap().into_indices()?, compact.rows)?;
+        let num_swaps = match arrays.next().unwrap().into_indices()?[..]
+        {
+            [s] => s,
+            _ => return Err(ReadError::Format("Invalid swap count.".to_string())),
+        };
+        Ok(LUP { compact, perm, num_swaps })
+    }
+}
+
+// An explicit q is stored as a 0 x 0 matrix when absent.
+impl Persist for QR<f64>
+{
+    const KIND: u16 = 6;
+
+    fn arrays(&self) -> Vec<Array>
+    {
+        let q = self.explicit_q.clone().unwrap_or(Mat::new((0, 0)));
+        vec![Array::from_mat(&self.compact), Array::from_slice(&self.tau), Array::from_mat(&q)]
+    }
+
+    fn from_arrays(arrays: Vec<Array>) -> Result<Self, ReadError>
+    {
+        let mut arrays = _expect(arrays, 3)?;
+        let compact = arrays.next().unwrap().into_mat::<f64>()?;
+        let tau = arrays.next().unwrap().into_vec::<f64>()?;
+        let q = arrays.next().unwrap().into_mat::<f64>()?;
+        let (m, n) = compact.shape();
+        let explicit_q = if q.rows == 0 && m > 0 { None } else { Some(_square(q, Some(m))?) };
+        let valid = match explicit_q
+        {
+            Some(_) => tau.is_empty(),
+            None => tau.len() == std::cmp::min(m, n),
+        };
+        if !valid
+        {
+            return Err(ReadError::Format("Reflectors do not match the shape of r.".to_string()));
+        }
+        Ok(QR { compact, tau, explicit_q })
+    }
+}
+
+impl Persist for EIG<f64>
+{
+    const KIND: u16 = 7;
+
+    fn arrays(&self) -> Vec<Array>
+    {
+        vec![Array::from_slice(&self.eigvals.iter().copied().collect::<Vec<f64>>()), Array::from_mat(&self.eigvecs)]
+    }
+
+    fn from_arrays(arrays: Vec<Array>) -> Result<Self, ReadError>
+    {
+        let mut arrays = _expect(arrays, 2)?;
+        let eigvals = arrays.next().unwrap().into_vec::<f64>()?;
+        let eigvecs = arrays.next().unwrap().into_mat::<f64>()?;
+        if eigvecs.cols != eigvals.len()
+        {
+            return Err(ReadError::Format("Expected one eigenvector per eigenvalue.".to_string()));
+        }
+        Ok(EIG { eigvals: Vector::from_vec(eigvals), eigvecs })
+    }
+}
+
+impl Persist for Cholesky<f64>
+{
+    const KIND: u16 = 8;
+
+    fn arrays(&self) -> Vec<Array>
+    {
+        vec![Array::from_mat(&self.l)]
+    }
+
+    fn from_arrays(arrays: Vec<Array>) -> Result<Self, ReadError>
+    {
+        Ok(Cholesky { l: _square(_expect(arrays, 1)?.next().unwrap().into_mat()?, None)? })
+    }
+}
+
+impl Persist for LDL<f64>
+{
+    const KIND: u16 = 9;
+
+    fn arrays(&self) -> Vec<Array>
+    {
+        vec![Array::from_mat(&self.l), Array::from_mat(&self.d), Array::from_indices(&self.perm)]
+    }
+
+    fn from_arrays(arrays: Vec<Array>) -> Result<Self, ReadError>
+    {
+        let mut arrays = _expect(arrays, 3)?;
+        let l = _square(arrays.next().unwrap().into_mat()?, None)?;
+        let d = _square(arrays.next().unwrap().into_mat()?, Some(l.rows))?;
+        let perm = _perm(arrays.next().unwrap().into_indices()?, l.rows)?;
+        Ok(LDL { l, d, perm })
+    }
+}
+
+// Read-only file contents, memory mapped on unix. Arrays are borrowed
+// without copying.
+pub struct Mapped
+{
+    storage: Storage,
+    kind: u16,
+    blocks: Vec<Block>,
+}
+
+enum Storage
+{
+    #[cfg(unix)]
+    Mapped(*const u8, usize),
+    // 8 byte aligned buffer on other platforms.
+    #[cfg_attr(unix, allow(dead_code))]
+    Owned(Vec<u64>, usize),
+}
+
+impl Mapped
+{
+    pub fn open(path: impl AsRef<Path>) -> Result<Mapped, ReadError>
+    {
+        let file = std::fs::File::open(path)?;
+        let len = file.metadata()?.len() as usize;
+        if len < BLOCK
+        {
+            return Err(ReadError::Format("Not a redpill binary file.".to_string()));
+        }
+        let storage = Self::_map(file, len)?;
+        let (kind, blocks) = _parse(Self::_bytes(&storage))?;
+        Ok(Mapped { storage, kind, blocks })
+    }
+
+    #[cfg(unix)]
+    fn _map(file: std::fs::File, len: usize) -> Result<Storage, ReadError>
+    {
+        use std::os::unix::io::AsRawFd;
+        let ptr = unsafe { libc::mmap(std::ptr::null_mut(), len, libc::PROT_READ, libc::MAP_PRIVATE, file.as_raw_fd(), 0) };
+        if ptr == libc::MAP_FAILED
+        {
+            return Err(ReadError::Io(std::io::Error::last_os_error()));
+        }
+        Ok(Storage::Mapped(ptr as *const u8, len))
+    }
+
+    #[cfg(not(unix))]
+    fn _map(mut file: std::fs::File, len: usize) -> Result<Storage, ReadError>
+    {
+        let mut buffer = vec![0u64; len.div_ceil(8)];
+        let bytes = unsafe { std::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, len) };
+        file.read_exact(bytes)?;
+        Ok(Storage::Owned(buffer, len))
+    }
+
+    fn _bytes(storage: &Storage) -> &[u8]
+    {
+        match storage
+        {
+            #[cfg(unix)]
+            Storage::Mapped(ptr, len) => unsafe { std::slice::from_raw_parts(*ptr, *len) },
+            Storage::Owned(buffer, len) => unsafe { std::slice::from_raw_parts(buffer.as_ptr() as *const u8, *len) },
+        }
+    }
+
+    pub fn kind(&self) -> u16
+    {
+        self.kind
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.blocks.len()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.blocks.is_empty()
+    }
+
+    // Elements of array i, in storage order.
+    pub fn slice<T: Element>(&self, i: usize) -> Result<&[T], ReadError>
+    {
+        if !cfg!(target_endian = "little")
+        {
+            return Err(ReadError::Format("Zero-copy access needs a little endian target.".to_string()));
+        }
+        let block = self.blocks.get(i).ok_or_else(|| ReadError::Format(format!("No array {}.", i)))?;
+        if block.dtype != _dtype::<T>()
+        {
+            return Err(ReadError::Format(format!("Expected dtype {}, found {}.", _dtype::<T>(), block.dtype)));
+        }
+        // Blocks start at multiples of 64 bytes of a page or u64 aligned buffer.
+        let bytes = &Self::_bytes(&self.storage)[block.start..block.end];
+        Ok(unsafe { std::slice::from_raw_parts(bytes.as_ptr() as *const T, bytes.len() / T::SIZE) })
+    }
+
+    pub fn mat<T: Element>(&self, i: usize) -> Result<MatView<'_, T>, ReadError>
+    {
+        let elements = self.slice::<T>(i)?;
+        let block = &self.blocks[i];
+        if block.shape.len() != 2
+        {
+            return Err(ReadError::Format(format!("Expected a 2-d array, found {}-d.", block.shape.len())));
+        }
+        let (rows, cols) = (block.shape[0], block.shape[1]);
+        let (row_stride, col_stride) = if block.layout == COL_MAJOR { (1, rows) } else { (cols, 1) };
+        Ok(MatView { rows, cols, row_stride, col_stride, elements })
+    }
+
+    pub fn load<P: Persist>(&self) -> Result<P, ReadError>
+    {
+        _check_kind::<P>(self.kind)?;
+        let bytes = Self::_bytes(&self.storage);
+        let arrays = self.blocks.iter()
+            .map(|b| Array { dtype: b.dtype, layout: b.layout, shape: b.shape.clone(), bytes: bytes[b.start..b.end].to_vec() })
+            .collect();
+        P::from_arrays(arrays)
+    }
+}
+
+impl Drop for Mapped
+{
+    fn drop(&mut self)
+    {
+        #[cfg(unix)]
+        if let Storage::Mapped(ptr, len) = self.storage
+        {
+            unsafe { libc::munmap(ptr as *mut _, len) };
+        }
+    }
+}
+
+// Borrowed matrix over mapped memory.
+pub struct MatView<'a, T>
+{
+    pub rows: usize,
+    pub cols: usize,
+    row_stride: usize,
+    col_stride: usize,
+    elements: &'a [T],
+}
+
+impl<T: Element> MatView<'_, T>
+{
+    pub fn shape(&self) -> (usize, usize)
+    {
+        (self.rows, self.cols)
+    }
+
+    pub fn to_mat(&self) -> Mat<T>
+    {
+        Mat::_from_strided(self.shape(), self.row_stride, self.col_stride, self.elements.to_vec())
+    }
+}
+
+impl<T> Index<(usize, usize)> for MatView<'_, T>
+{
+    type Output = T;
+    fn index(&self, index: (usize, usize)) -> &Self::Output
+    {
+        assert!(index.0 < self.rows && index.1 < self.cols);
+        &self.elements[index.0 * self.row_stride + index.1 * self.col_stride]
+    }
+}
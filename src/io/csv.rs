@@ -0,0 +1,194 @@
+use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
+
+use crate::matrix::Mat;
+use crate::io::ReadError;
+
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Missing
+{
+    Nan,
+    Error,
+}
+
+#[derive(Clone, Debug)]
+pub struct CsvOptions
+{
+    pub delimiter: char,
+    // Number of leading lines skipped when reading.
+    pub skip_lines: usize,
+    // How empty fields are read.
+    pub missing: Missing,
+    // Digits after the decimal point when writing, shortest exact
+    // representation if None.
+    pub precision: Option<usize>,
+    // Header line written before the rows.
+    pub column_names: Option<Vec<String>>,
+}
+
+impl Default for CsvOptions
+{
+    fn default() -> Self
+    {
+        CsvOptions { delimiter: ',', skip_lines: 0, missing: Missing::Error, precision: None, column_names: None }
+    }
+}
+
+impl Mat<f64>
+{
+    // Reads rows one line at a time, blank lines are ignored. Fields may be
+    // quoted with "" standing for a literal quote, but a quoted field cannot
+    // span lines.
+    pub fn from_csv<R: Read>(reader: R, options: &CsvOptions) -> Result<Mat<f64>, ReadError>
+    {
+        let mut reader = BufReader::new(reader);
+        let mut line = String::new();
+        let mut elements = Vec::new();
+        let (mut rows, mut cols) = (0, 0);
+        let mut n = 0;
+
+        loop
+        {
+            line.clear();
+            if reader.read_line(&mut line)? == 0
+            {
+                break;
+            }
+            n += 1;
+            let trimmed = line.trim_end_matches(['\n', '\r']);
+            if n <= options.skip_lines || trimmed.trim().is_empty()
+            {
+                continue;
+            }
+
+            let before = elements.len();
+            for field in _split(trimmed, options.delimiter).map_err(|message| ReadError::parse(n, message))?
+            {
+                let field = field.trim();
+                if field.is_empty()
+                {
+                    match options.missing
+                    {
+                        Missing::Nan => elements.push(f64::NAN),
+                        Missing::Error => return Err(ReadError::parse(n, format!("Missing value in column {}.", elements.len() - before + 1))),
+                    }
+                    continue;
+                }
+                let value = field.parse::<f64>().map_err(|_| ReadError::parse(n, format!("Invalid value '{}'.", field)))?;
+                elements.push(value);
+            }
+
+            let width = elements.len() - before;
+            if rows == 0
+            {
+                cols = width;
+            }
+            else if width != cols
+            {
+                return Err(ReadError::parse(n, format!("Expected {} columns, found {}.", cols, width)));
+            }
+            rows += 1;
+        }
+        Ok(Mat::from_vec((rows, cols), elements))
+    }
+
+    pub fn write_csv<W: Write>(&self, writer: W, options: &CsvOptions) -> std::io::Result<()>
+    {
+        let mut writer = std::io::BufWriter::new(writer);
+        let delimiter = options.delimiter.to_string();
+        if let Some(names) = &options.column_names
+        {
+            if names.len() != self.cols
+            {
+                let message = format!("{} column names for {} columns.", names.len(), self.cols);
+                return Err(std::io::Error::new(ErrorKind::InvalidInput, message));
+            }
+            let names = names.iter().map(|name| _quote(name, options.delimiter)).collect::<Vec<String>>();
+            writeln!(writer, "{}", names.join(&delimiter))?;
+        }
+        for r in 0..self.rows
+        {
+            for c in 0..self.cols
+            {
+                if c > 0
+                {
+                    write!(writer, "{}", delimiter)?;
+                }
+                match options.precision
+                {
+                    Some(p) => write!(writer, "{:.*}", p, self[(r, c)])?,
+                    None => write!(writer, "{}", self[(r, c)])?,
+                }
+            }
+            writeln!(writer)?;
+        }
+        writer.flush()
+    }
+}
+
+// Splits a line on the delimiter outside of double quotes and unquotes the
+// fields, whitespace around a quoted field is dropped.
+fn _split(line: &str, delimiter: char) -> Result<Vec<String>, &'static str>
+{
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quoted = false;
+    while let Some(ch) = chars.next()
+    {
+        if quoted
+        {
+            if ch != '"'
+            {
+                field.push(ch);
+            }
+            else if chars.peek() == Some(&'"')
+            {
+                field.push(chars.next().unwrap());
+            }
+            else
+            {
+                quoted = false;
+                while chars.peek().is_some_and(|c| *c != delimiter)
+                {
+                    if !chars.next().unwrap().is_whitespace()
+                    {
+                        return Err("Text after a closing quote.");
+                    }
+                }
+            }
+        }
+        else if ch == delimiter
+        {
+            fields.push(std::mem::take(&mut field));
+        }
+        else if ch == '"' && field.trim().is_empty()
+        {
+            field.clear();
+            quoted = true;
+        }
+        else
+        {
+            field.push(ch);
+        }
+    }
+    if quoted
+    {
+        return Err("Unterminated quote.");
+    }
+    fields.push(field);
+    Ok(fields)
+}
+
+// Quotes a header name holding the delimiter, a quote or surrounding whitespace.
+fn _quote(name: &str, delimiter: char) -> String
+{
+    if name.contains(delimiter) || name.contains('"') || name.trim() != name
+    {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+    else
+    {
+        name.to_string()
+    }
+}
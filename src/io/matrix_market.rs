@@ -0,0 +1,374 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
+
+use crate::matrix::Mat;
+use crate::sparse::Coo;
+use crate::io::ReadError;
+
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format
+{
+    Coordinate,
+    Array,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Field
+{
+    Real,
+    Integer,
+    Pattern,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Symmetry
+{
+    General,
+    Symmetric,
+    SkewSymmetric,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Header
+{
+    pub format: Format,
+    pub field: Field,
+    pub symmetry: Symmetry,
+}
+
+pub fn read_dense<R: Read>(reader: R) -> Result<Mat<f64>, ReadError>
+{
+    let (_, coo) = _read(reader)?;
+    Ok(coo.to_dense())
+}
+
+// Array files keep their nonzero entries only.
+pub fn read_sparse<R: Read>(reader: R) -> Result<Coo<f64>, ReadError>
+{
+    let (header, mut coo) = _read(reader)?;
+    if header.format == Format::Array
+    {
+        coo.entries.retain(|e| e.2 != 0.0);
+    }
+    Ok(coo)
+}
+
+// Writes in array format, only the lower triangle is written for symmetric
+// and skew-symmetric matrices. A matrix that does not have the requested
+// symmetry is an InvalidInput error, nothing is written then.
+pub fn write_dense<W: Write>(mut writer: W, mat: &Mat<f64>, field: Field, symmetry: Symmetry) -> std::io::Result<()>
+{
+    if field == Field::Pattern
+    {
+        return Err(_invalid("Pattern matrices need the coordinate format."));
+    }
+    _check_shape(mat.rows, mat.cols, symmetry)?;
+    let mut values = Vec::new();
+    for c in 0..mat.cols
+    {
+        for r in 0..mat.rows
+        {
+            if symmetry != Symmetry::General && !_mirrors(symmetry, mat[(r, c)], mat[(c, r)])
+            {
+                return Err(_invalid("Matrix does not have the requested symmetry."));
+            }
+            if _stored(symmetry, r, c)
+            {
+                values.push(_value(field, mat[(r, c)])?);
+            }
+        }
+    }
+
+    _write_header(&mut writer, Header { format: Format::Array, field, symmetry })?;
+    writeln!(writer, "{} {}", mat.rows, mat.cols)?;
+    for v in values
+    {
+        writeln!(writer, "{}", v)?;
+    }
+    Ok(())
+}
+
+// Writes in coordinate format, entries above the diagonal are skipped for
+// symmetric and skew-symmetric matrices. Duplicate entries are summed when
+// checking the symmetry, like to_dense does.
+pub fn write_sparse<W: Write>(mut writer: W, coo: &Coo<f64>, field: Field, symmetry: Symmetry) -> std::io::Result<()>
+{
+    if field == Field::Pattern && symmetry == Symmetry::SkewSymmetric
+    {
+        return Err(_invalid("Pattern matrices cannot be skew-symmetric."));
+    }
+    _check_shape(coo.rows, coo.cols, symmetry)?;
+    if symmetry != Symmetry::General
+    {
+        let mut sums = HashMap::new();
+        for &(r, c, v) in coo.entries.iter()
+        {
+            *sums.entry((r, c)).or_insert(0.0) += v;
+        }
+        for (&(r, c), &v) in sums.iter()
+        {
+            let mirrored = sums.get(&(c, r));
+            let valid = match field
+            {
+                Field::Pattern => mirrored.is_some(),
+                _ => _mirrors(symmetry, v, mirrored.copied().unwrap_or(0.0)),
+            };
+            if !valid
+            {
+                return Err(_invalid("Matrix does not have the requested symmetry."));
+            }
+        }
+    }
+
+    let mut lines = Vec::new();
+    for &(r, c, v) in coo.entries.iter().filter(|e| _stored(symmetry, e.0, e.1))
+    {
+        match field
+        {
+            Field::Pattern => lines.push(format!("{} {}", r + 1, c + 1)),
+            _ => lines.push(format!("{} {} {}", r + 1, c + 1, _value(field, v)?)),
+        }
+    }
+    _write_header(&mut writer, Header { format: Format::Coordinate, field, symmetry })?;
+    writeln!(writer, "{} {} {}", coo.rows, coo.cols, lines.len())?;
+    for line in lines
+    {
+        writeln!(writer, "{}", line)?;
+    }
+    Ok(())
+}
+
+fn _invalid(message: &'static str) -> std::io::Error
+{
+    std::io::Error::new(ErrorKind::InvalidInput, message)
+}
+
+fn _check_shape(rows: usize, cols: usize, symmetry: Symmetry) -> std::io::Result<()>
+{
+    if symmetry != Symmetry::General && rows != cols
+    {
+        return Err(_invalid("Symmetric and skew-symmetric matrices must be square."));
+    }
+    Ok(())
+}
+
+// Whether v at (r, c) and mirrored at (c, r) agree with the symmetry, the
+// diagonal of a skew-symmetric matrix is zero.
+fn _mirrors(symmetry: Symmetry, v: f64, mirrored: f64) -> bool
+{
+    match symmetry
+    {
+        Symmetry::General => true,
+        Symmetry::Symmetric => v == mirrored,
+        Symmetry::SkewSymmetric => v == -mirrored,
+    }
+}
+
+fn _write_header<W: Write>(writer: &mut W, header: Header) -> std::io::Result<()>
+{
+    let format = match header.format { Format::Coordinate => "coordinate", Format::Array => "array" };
+    let field = match header.field { Field::Real => "real", Field::Integer => "integer", Field::Pattern => "pattern" };
+    let symmetry = match header.symmetry
+    {
+        Symmetry::General => "general",
+        Symmetry::Symmetric => "symmetric",
+        Symmetry::SkewSymmetric => "skew-symmetric",
+    };
+    writeln!(writer, "%%MatrixMarket matrix {} {} {}", format, field, symmetry)
+}
+
+fn _value(field: Field, v: f64) -> std::io::Result<String>
+{
+    match field
+    {
+        Field::Integer =>
+        {
+            if v.fract() != 0.0 || v.abs() > i64::MAX as f64
+            {
+                return Err(_invalid("Integer field with a non integer value."));
+            }
+            Ok(format!("{}", v as i64))
+        }
+        _ => Ok(format!("{:e}", v)),
+    }
+}
+
+// Whether entry (r, c) is part of the stored triangle.
+fn _stored(symmetry: Symmetry, r: usize, c: usize) -> bool
+{
+    match symmetry
+    {
+        Symmetry::General => true,
+        Symmetry::Symmetric => r >= c,
+        Symmetry::SkewSymmetric => r > c,
+    }
+}
+
+fn _read<R: Read>(reader: R) -> Result<(Header, Coo<f64>), ReadError>
+{
+    let mut lines = BufReader::new(reader).lines().enumerate().map(|(i, l)| (i + 1, l));
+    let first = match lines.next()
+    {
+        Some((_, line)) => line?,
+        None => return Err(ReadError::parse(1, "Empty file.")),
+    };
+    let header = _parse_header(&first).map_err(|m| ReadError::parse(1, m))?;
+    let mut last = 1;
+
+    let (n, size) = _next_line(&mut lines, &mut last)?
+        .ok_or_else(|| ReadError::parse(last + 1, "Missing size line."))?;
+    let size = size.split_whitespace()
+        .map(|t| t.parse::<usize>().map_err(|_| ReadError::parse(n, format!("Invalid size '{}'.", t))))
+        .collect::<Result<Vec<usize>, ReadError>>()?;
+    let expected = if header.format == Format::Coordinate { 3 } else { 2 };
+    if size.len() != expected
+    {
+        return Err(ReadError::parse(n, format!("Expected {} sizes, found {}.", expected, size.len())));
+    }
+    let (rows, cols) = (size[0], size[1]);
+    if header.symmetry != Symmetry::General && rows != cols
+    {
+        return Err(ReadError::parse(n, "Symmetric matrices must be square."));
+    }
+
+    let mut coo = Coo::new((rows, cols));
+    match header.format
+    {
+        Format::Coordinate =>
+        {
+            for k in 0..size[2]
+            {
+                let (n, line) = _next_line(&mut lines, &mut last)?
+                    .ok_or_else(|| ReadError::parse(last + 1, format!("Expected {} entries, found {}.", size[2], k)))?;
+                let tokens = line.split_whitespace().collect::<Vec<&str>>();
+                let expected = if header.field == Field::Pattern { 2 } else { 3 };
+                if tokens.len() != expected
+                {
+                    return Err(ReadError::parse(n, format!("Expected {} values, found {}.", expected, tokens.len())));
+                }
+                let r = _parse_index(tokens[0], rows, n)?;
+                let c = _parse_index(tokens[1], cols, n)?;
+                let v = if header.field == Field::Pattern { 1.0 } else { _parse_value(tokens[2], header.field, n)? };
+                _push(&mut coo, header.symmetry, r, c, v, n)?;
+            }
+        }
+        Format::Array =>
+        {
+            for c in 0..cols
+            {
+                for r in 0..rows
+                {
+                    if !_stored(header.symmetry, r, c)
+                    {
+                        continue;
+                    }
+                    let (n, line) = _next_line(&mut lines, &mut last)?
+                        .ok_or_else(|| ReadError::parse(last + 1, "Too few entries."))?;
+                    let tokens = line.split_whitespace().collect::<Vec<&str>>();
+                    if tokens.len() != 1
+                    {
+                        return Err(ReadError::parse(n, format!("Expected 1 value, found {}.", tokens.len())));
+                    }
+                    let v = _parse_value(tokens[0], header.field, n)?;
+                    _push(&mut coo, header.symmetry, r, c, v, n)?;
+                }
+            }
+        }
+    }
+
+    if let Some((n, _)) = _next_line(&mut lines, &mut last)?
+    {
+        return Err(ReadError::parse(n, "Unexpected data after the last entry."));
+    }
+    Ok((header, coo))
+}
+
+fn _parse_header(line: &str) -> Result<Header, String>
+{
+    let tokens = line.split_whitespace().map(|t| t.to_lowercase()).collect::<Vec<String>>();
+    if tokens.len() != 5 || tokens[0] != "%%matrixmarket"
+    {
+        return Err("Expected '%%MatrixMarket matrix <format> <field> <symmetry>'.".to_string());
+    }
+    if tokens[1] != "matrix"
+    {
+        return Err(format!("Unsupported object '{}'.", tokens[1]));
+    }
+    let format = match tokens[2].as_str()
+    {
+        "coordinate" => Format::Coordinate,
+        "array" => Format::Array,
+        other => return Err(format!("Unsupported format '{}'.", other)),
+    };
+    let field = match tokens[3].as_str()
+    {
+        "real" | "double" => Field::Real,
+        "integer" => Field::Integer,
+        "pattern" if format == Format::Coordinate => Field::Pattern,
+        other => return Err(format!("Unsupported field '{}' for the {} format.", other, tokens[2])),
+    };
+    let symmetry = match tokens[4].as_str()
+    {
+        "general" => Symmetry::General,
+        "symmetric" => Symmetry::Symmetric,
+        "skew-symmetric" => Symmetry::SkewSymmetric,
+        other => return Err(format!("Unsupported symmetry '{}'.", other)),
+    };
+    Ok(Header { format, field, symmetry })
+}
+
+// Next line that is neither blank nor a comment.
+fn _next_line<I>(lines: &mut I, last: &mut usize) -> Result<Option<(usize, String)>, ReadError>
+where I: Iterator<Item = (usize, std::io::Result<String>)>
+{
+    for (n, line) in lines
+    {
+        *last = n;
+        let line = line?;
+        let trimmed = line.trim();
+        if !trimmed.is_empty() && !trimmed.starts_with('%')
+        {
+            return Ok(Some((n, trimmed.to_string())));
+        }
+    }
+    Ok(None)
+}
+
+// One based index in 1..=size to zero based.
+fn _parse_index(token: &str, size: usize, line: usize) -> Result<usize, ReadError>
+{
+    match token.parse::<usize>()
+    {
+        Ok(i) if i >= 1 && i <= size => Ok(i - 1),
+        Ok(i) => Err(ReadError::parse(line, format!("Index {} out of range 1..={}.", i, size))),
+        Err(_) => Err(ReadError::parse(line, format!("Invalid index '{}'.", token))),
+    }
+}
+
+fn _parse_value(token: &str, field: Field, line: usize) -> Result<f64, ReadError>
+{
+    match field
+    {
+        Field::Integer => token.parse::<i64>().map(|v| v as f64).ok(),
+        _ => token.parse::<f64>().ok(),
+    }
+    .ok_or_else(|| ReadError::parse(line, format!("Invalid value '{}'.", token)))
+}
+
+// Adds an entry and its mirror image for symmetric matrices.
+fn _push(coo: &mut Coo<f64>, symmetry: Symmetry, r: usize, c: usize, v: f64, line: usize) -> Result<(), ReadError>
+{
+    if !_stored(symmetry, r, c)
+    {
+        return Err(ReadError::parse(line, "Entry outside the lower triangle of a symmetric matrix."));
+    }
+    coo.push(r, c, v);
+    match symmetry
+    {
+        Symmetry::Symmetric if r != c => coo.push(c, r, v),
+        Symmetry::SkewSymmetric => coo.push(c, r, -v),
+        _ => {}
+    }
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use std::fmt;
+
+pub mod matrix_market;
+pub mod npy;
+pub mod csv;
+pub mod binary;
+
+
+#[derive(Debug)]
+pub enum ReadError
+{
+    Io(std::io::Error),
+    // Line numbers start at 1.
+    Parse { line: usize, message: String },
+    // Malformed or mismatching binary data.
+    Format(String),
+}
+
+impl ReadError
+{
+    pub(crate) fn parse(line: usize, message: impl Into<String>) -> Self
+    {
+        ReadError::Parse { line, message: message.into() }
+    }
+}
+
+impl fmt::Display for ReadError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            ReadError::Io(err) => write!(f, "{}", err),
+            ReadError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            ReadError::Format(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for ReadError
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)>
+    {
+        match self
+        {
+            ReadError::Io(err) => Some(err),
+            ReadError::Parse { .. } | ReadError::Format(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ReadError
+{
+    fn from(err: std::io::Error) -> Self
+    {
+        ReadError::Io(err)
+    }
+}
+
+pub(crate) fn _crc32(bytes: &[u8]) -> u32
+{
+    let mut crc = !0u32;
+    for &b in bytes
+    {
+        crc ^= b as u32;
+        for _ in 0..8
+        {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xedb88320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
@@ -0,0 +1,405 @@
+use std::io::{Read, Write};
+
+use crate::matrix::Mat;
+use crate::vector::Vector;
+use crate::io::{ReadError, _crc32};
+
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+// Element types with a NumPy dtype, written little endian.
+pub trait Element: Copy + Default
+{
+    // Type kind and size in bytes, as in '<f8'.
+    const KIND: char;
+    const SIZE: usize;
+
+    fn from_bytes(bytes: &[u8], little_endian: bool) -> Self;
+    fn to_le(self, out: &mut Vec<u8>);
+}
+
+macro_rules! impl_element
+{
+    ($t:ty, $kind:expr) =>
+    {
+        impl Element for $t
+        {
+            const KIND: char = $kind;
+            const SIZE: usize = std::mem::size_of::<$t>();
+
+            fn from_bytes(bytes: &[u8], little_endian: bool) -> Self
+            {
+                let bytes = bytes.try_into().unwrap();
+                if little_endian { <$t>::from_le_bytes(bytes) } else { <$t>::from_be_bytes(bytes) }
+            }
+
+            fn to_le(self, out: &mut Vec<u8>)
+            {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+        }
+    };
+}
+
+impl_element!(f32, 'f');
+impl_element!(f64, 'f');
+impl_element!(i64, 'i');
+impl_element!(u64, 'u');
+
+pub fn read_mat<T: Element, R: Read>(mut reader: R) -> Result<Mat<T>, ReadError>
+{
+    let (shape, fortran_order, elements) = _read_array::<T, R>(&mut reader)?;
+    if shape.len() != 2
+    {
+        return Err(ReadError::Format(format!("Expected a 2-d array, found shape {:?}.", shape)));
+    }
+    let (rows, cols) = (shape[0], shape[1]);
+    Ok(if fortran_order
+    {
+        Mat::_from_strided((rows, cols), 1, rows, elements)
+    }
+    else
+    {
+        Mat::_from_strided((rows, cols), cols, 1, elements)
+    })
+}
+
+pub fn read_vector<T: Element, R: Read>(mut reader: R) -> Result<Vector<T>, ReadError>
+{
+    let (shape, _, elements) = _read_array::<T, R>(&mut reader)?;
+    if shape.len() != 1
+    {
+        return Err(ReadError::Format(format!("Expected a 1-d array, found shape {:?}.", shape)));
+    }
+    Ok(Vector::from_vec(elements))
+}
+
+// Contiguous matrices are written as they are stored, in C or Fortran order.
+pub fn write_mat<T: Element, W: Write>(mut writer: W, mat: &Mat<T>) -> std::io::Result<()>
+{
+    let (fortran_order, elements) = if mat._is_row_major()
+    {
+        (false, mat._elements().to_vec())
+    }
+    else if mat._is_col_major()
+    {
+        (true, mat._elements().to_vec())
+    }
+    else
+    {
+        let mut elements = Vec::with_capacity(mat.rows * mat.cols);
+        for r in 0..mat.rows
+        {
+            for c in 0..mat.cols
+            {
+                elements.push(mat[(r, c)]);
+            }
+        }
+        (false, elements)
+    };
+    writer.write_all(&_array_bytes(&[mat.rows, mat.cols], fortran_order, &elements))
+}
+
+pub fn write_vector<T: Element, W: Write>(mut writer: W, vector: &Vector<T>) -> std::io::Result<()>
+{
+    let elements = vector.iter().copied().collect::<Vec<T>>();
+    writer.write_all(&_array_bytes(&[vector.len()], false, &elements))
+}
+
+fn _descr<T: Element>() -> String
+{
+    format!("<{}{}", T::KIND, T::SIZE)
+}
+
+fn _array_bytes<T: Element>(shape: &[usize], fortran_order: bool, elements: &[T]) -> Vec<u8>
+{
+    let shape = match shape.len()
+    {
+        1 => format!("({},)", shape[0]),
+        _ => format!("({})", shape.iter().map(|s| s.to_string()).collect::<Vec<String>>().join(", ")),
+    };
+    let mut header = format!("{{'descr': '{}', 'fortran_order': {}, 'shape': {}, }}",
+        _descr::<T>(), if fortran_order { "True" } else { "False" }, shape);
+
+    // The data starts at a multiple of 64 bytes, the header ends with a newline.
+    let prefix = if header.len() + 11 <= u16::MAX as usize { 10 } else { 12 };
+    let total = (prefix + header.len() + 1).div_ceil(64) * 64;
+    header.push_str(&" ".repeat(total - prefix - header.len() - 1));
+    header.push('\n');
+
+    let mut bytes = Vec::with_capacity(total + elements.len() * T::SIZE);
+    bytes.extend_from_slice(MAGIC);
+    if prefix == 10
+    {
+        bytes.extend_from_slice(&[1, 0]);
+        bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    }
+    else
+    {
+        bytes.extend_from_slice(&[2, 0]);
+        bytes.extend_from_slice(&(header.len() as u32).to_le_bytes());
+    }
+    bytes.extend_from_slice(header.as_bytes());
+    for &e in elements
+    {
+        e.to_le(&mut bytes);
+    }
+    bytes
+}
+
+fn _read_array<T: Element, R: Read>(reader: &mut R) -> Result<(Vec<usize>, bool, Vec<T>), ReadError>
+{
+    let mut prefix = [0u8; 8];
+    reader.read_exact(&mut prefix)?;
+    if &prefix[..6] != MAGIC
+    {
+        return Err(ReadError::Format("Not a .npy file.".to_string()));
+    }
+    let header_len = match prefix[6]
+    {
+        1 =>
+        {
+            let mut len = [0u8; 2];
+            reader.read_exact(&mut len)?;
+            u16::from_le_bytes(len) as usize
+        }
+        2 | 3 =>
+        {
+            let mut len = [0u8; 4];
+            reader.read_exact(&mut len)?;
+            u32::from_le_bytes(len) as usize
+        }
+        version => return Err(ReadError::Format(format!("Unsupported .npy version {}.", version))),
+    };
+    let header = _read_len(reader, header_len)?;
+    let header = String::from_utf8(header).map_err(|_| ReadError::Format("Header is not valid text.".to_string()))?;
+
+    let descr = _dict_value(&header, "descr")
+        .and_then(|v| v.strip_prefix('\''))
+        .and_then(|v| v.split('\'').next())
+        .ok_or_else(|| ReadError::Format("Header has no 'descr'.".to_string()))?;
+    let fortran_order = match _dict_value(&header, "fortran_order")
+    {
+        Some(v) if v.starts_with("True") => true,
+        Some(v) if v.starts_with("False") => false,
+        _ => return Err(ReadError::Format("Header has no 'fortran_order'.".to_string())),
+    };
+    let shape = _dict_value(&header, "shape")
+        .and_then(|v| v.strip_prefix('('))
+        .and_then(|v| v.split(')').next())
+        .ok_or_else(|| ReadError::Format("Header has no 'shape'.".to_string()))?
+        .split(',')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse::<usize>().map_err(|_| ReadError::Format(format!("Invalid shape entry '{}'.", s))))
+        .collect::<Result<Vec<usize>, ReadError>>()?;
+
+    let mut chars = descr.chars();
+    let little_endian = match chars.next()
+    {
+        Some('<') | Some('|') => true,
+        Some('>') => false,
+        Some('=') => cfg!(target_endian = "little"),
+        _ => return Err(ReadError::Format(format!("Invalid dtype '{}'.", descr))),
+    };
+    if chars.as_str() != &_descr::<T>()[1..]
+    {
+        return Err(ReadError::Format(format!("Expected dtype '{}', found '{}'.", _descr::<T>(), descr)));
+    }
+
+    let len = shape.iter()
+        .try_fold(T::SIZE, |acc, &d| acc.checked_mul(d))
+        .ok_or_else(|| ReadError::Format(format!("Shape {:?} is too large.", shape)))?;
+    let data = _read_len(reader, len)?;
+    let elements = data.chunks_exact(T::SIZE).map(|b| T::from_bytes(b, little_endian)).collect::<Vec<T>>();
+    Ok((shape, fortran_order, elements))
+}
+
+// Reads exactly len bytes, the buffer only grows with the data actually read
+// so a bogus length in a header cannot allocate ahead of it.
+fn _read_len<R: Read>(reader: &mut R, len: usize) -> Result<Vec<u8>, ReadError>
+{
+    let mut data = Vec::new();
+    reader.take(len as u64).read_to_end(&mut data)?;
+    if data.len() != len
+    {
+        return Err(ReadError::Io(std::io::Error::from(std::io::ErrorKind::UnexpectedEof)));
+    }
+    Ok(data)
+}
+
+// Text following 'key': in a Python dict literal.
+fn _dict_value<'a>(header: &'a str, key: &str) -> Option<&'a str>
+{
+    let quoted = format!("'{}'", key);
+    let start = header.find(&quoted)? + quoted.len();
+    header[start..].trim_start().strip_prefix(':').map(|v| v.trim_start())
+}
+
+// Uncompressed .npz archive, a zip of .npy files keyed by name without the
+// .npy extension.
+pub struct Npz
+{
+    entries: Vec<(String, Vec<u8>)>,
+}
+
+impl Npz
+{
+    pub fn read<R: Read>(mut reader: R) -> Result<Npz, ReadError>
+    {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        let bad = |message: &str| ReadError::Format(message.to_string());
+        let u16_at = |at: usize| -> Result<usize, ReadError>
+        {
+            data.get(at..at + 2).map(|b| u16::from_le_bytes([b[0], b[1]]) as usize).ok_or_else(|| bad("Truncated archive."))
+        };
+        let u32_at = |at: usize| -> Result<usize, ReadError>
+        {
+            data.get(at..at + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize).ok_or_else(|| bad("Truncated archive."))
+        };
+
+        // The end of central directory record sits at the end, possibly
+        // followed by a comment.
+        let end = (0..data.len().saturating_sub(21)).rev()
+            .find(|&i| data[i..i + 4] == [0x50, 0x4b, 0x05, 0x06])
+            .ok_or_else(|| bad("Not a zip archive."))?;
+        let count = u16_at(end + 10)?;
+        let mut at = u32_at(end + 16)?;
+
+        let mut entries = Vec::with_capacity(count);
+        for _ in 0..count
+        {
+            if u32_at(at)? != 0x02014b50
+            {
+                return Err(bad("Corrupt central directory."));
+            }
+            if u16_at(at + 10)? != 0
+            {
+                return Err(bad("Compressed .npz archives are not supported."));
+            }
+            let size = u32_at(at + 20)?;
+            let (name_len, extra_len, comment_len) = (u16_at(at + 28)?, u16_at(at + 30)?, u16_at(at + 32)?);
+            let offset = u32_at(at + 42)?;
+            if size == u32::MAX as usize || offset == u32::MAX as usize
+            {
+                return Err(bad("Zip64 archives are not supported."));
+            }
+            let name = data.get(at + 46..at + 46 + name_len).ok_or_else(|| bad("Truncated archive."))?;
+            let name = String::from_utf8_lossy(name).to_string();
+
+            let start = offset + 30 + u16_at(offset + 26)? + u16_at(offset + 28)?;
+            let bytes = data.get(start..start + size).ok_or_else(|| bad("Truncated archive."))?.to_vec();
+            let key = name.strip_suffix(".npy").unwrap_or(&name).to_string();
+            entries.push((key, bytes));
+            at += 46 + name_len + extra_len + comment_len;
+        }
+        Ok(Npz { entries })
+    }
+
+    pub fn names(&self) -> Vec<&str>
+    {
+        self.entries.iter().map(|e| e.0.as_str()).collect()
+    }
+
+    pub fn mat<T: Element>(&self, name: &str) -> Result<Mat<T>, ReadError>
+    {
+        read_mat(self._entry(name)?)
+    }
+
+    pub fn vector<T: Element>(&self, name: &str) -> Result<Vector<T>, ReadError>
+    {
+        read_vector(self._entry(name)?)
+    }
+
+    fn _entry(&self, name: &str) -> Result<&[u8], ReadError>
+    {
+        self.entries.iter().find(|e| e.0 == name).map(|e| e.1.as_slice())
+            .ok_or_else(|| ReadError::Format(format!("No array named '{}'.", name)))
+    }
+}
+
+// Writes an uncompressed .npz archive, entries are stored as name.npy.
+pub struct NpzWriter<W: Write>
+{
+    writer: W,
+    offset: usize,
+    directory: Vec<u8>,
+    count: usize,
+}
+
+impl<W: Write> NpzWriter<W>
+{
+    pub fn new(writer: W) -> Self
+    {
+        NpzWriter { writer, offset: 0, directory: Vec::new(), count: 0 }
+    }
+
+    pub fn add_mat<T: Element>(&mut self, name: &str, mat: &Mat<T>) -> std::io::Result<()>
+    {
+        let mut bytes = Vec::new();
+        write_mat(&mut bytes, mat)?;
+        self._add(name, &bytes)
+    }
+
+    pub fn add_vector<T: Element>(&mut self, name: &str, vector: &Vector<T>) -> std::io::Result<()>
+    {
+        let mut bytes = Vec::new();
+        write_vector(&mut bytes, vector)?;
+        self._add(name, &bytes)
+    }
+
+    // Writes the central directory and returns the inner writer.
+    pub fn finish(mut self) -> std::io::Result<W>
+    {
+        let mut end = Vec::with_capacity(22);
+        end.extend_from_slice(&0x06054b50u32.to_le_bytes());
+        end.extend_from_slice(&[0, 0, 0, 0]);
+        end.extend_from_slice(&(self.count as u16).to_le_bytes());
+        end.extend_from_slice(&(self.count as u16).to_le_bytes());
+        end.extend_from_slice(&(self.directory.len() as u32).to_le_bytes());
+        end.extend_from_slice(&(self.offset as u32).to_le_bytes());
+        end.extend_from_slice(&[0, 0]);
+        self.writer.write_all(&self.directory)?;
+        self.writer.write_all(&end)?;
+        Ok(self.writer)
+    }
+
+    fn _add(&mut self, name: &str, bytes: &[u8]) -> std::io::Result<()>
+    {
+        let name = format!("{}.npy", name);
+        if self.offset + bytes.len() + name.len() + 30 > u32::MAX as usize || self.count == u16::MAX as usize
+        {
+            return Err(std::io::Error::other("Archive too large for a zip without zip64."));
+        }
+        let crc = _crc32(bytes);
+
+        // Fields shared by the local and the central header: version, flags,
+        // stored method, 1980-01-01 00:00, crc and sizes.
+        let mut common = Vec::with_capacity(26);
+        common.extend_from_slice(&[20, 0, 0, 0, 0, 0, 0, 0, 0x21, 0]);
+        common.extend_from_slice(&crc.to_le_bytes());
+        common.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        common.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        common.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        common.extend_from_slice(&[0, 0]);
+
+        let mut local = Vec::with_capacity(30 + name.len());
+        local.extend_from_slice(&0x04034b50u32.to_le_bytes());
+        local.extend_from_slice(&common);
+        local.extend_from_slice(name.as_bytes());
+        self.writer.write_all(&local)?;
+        self.writer.write_all(bytes)?;
+
+        self.directory.extend_from_slice(&0x02014b50u32.to_le_bytes());
+        self.directory.extend_from_slice(&[20, 0]);
+        self.directory.extend_from_slice(&common);
+        self.directory.extend_from_slice(&[0; 10]);
+        self.directory.extend_from_slice(&(self.offset as u32).to_le_bytes());
+        self.directory.extend_from_slice(name.as_bytes());
+
+        self.offset += local.len() + bytes.len();
+        self.count += 1;
+        Ok(())
+    }
+}
@@ -1,3 +1,17 @@
 pub mod matrix;
 pub mod vector;
+pub mod permutation;
+pub mod functions;
+pub mod eigensolvers;
+pub mod equations;
 pub mod decompositions;
+pub mod sparse;
+pub mod io;
+pub mod random;
+pub mod reductions;
+#[cfg(feature = "blas")]
+pub mod blas;
+mod constructors;
+mod format;
+#[cfg(feature = "serde")]
+mod serialization;
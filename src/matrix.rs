@@ -85,6 +85,17 @@ macro_rules! colvecs {
     );
 }
 
+// Rows are collected into an array of arrays, so ragged rows fail to compile.
+#[macro_export]
+macro_rules! mat {
+    () => (
+        $crate::matrix::Mat::new((0, 0))
+    );
+    ($($($item:expr),+ $(,)?);+ $(;)?) => (
+        $crate::matrix::Mat::from_array([$([$($item),+]),+])
+    );
+}
+
 impl<T> Mat<T>
 where T: Default + Clone + Sized
 {
@@ -99,19 +110,24 @@ where T: Default + Clone + Sized
         Self { rows: shape.0, cols: shape.1, row_stride: shape.1, col_stride: 1, elements: vec }
     }
 
+    pub fn from_array<const R: usize, const C: usize>(array: [[T; C]; R]) -> Self
+    {
+        Self { rows: R, cols: C, row_stride: C, col_stride: 1, elements: array.into_iter().flatten().collect() }
+    }
+
 
     pub fn shape(&self) -> (usize, usize)
     {
         (self.rows, self.cols)
     }
 
-    pub fn iter_trace(&self) -> TraceIter<T>
+    pub fn iter_trace(&self) -> TraceIter<'_, T>
     {
         let n = std::cmp::min(self.rows, self.cols);
         TraceIter { matrix: self, current: 0, min_dim: n }
     }
 
-    pub fn iter_submat(&self, rmin: usize, rmax: usize, cmin: usize, cmax: usize) -> Result<SubMatIter<T>, &str>
+    pub fn iter_submat(&self, rmin: usize, rmax: usize, cmin: usize, cmax: usize) -> Result<SubMatIter<'_, T>, &str>
     {
         if (rmin > rmax) || (cmin > cmax) || (rmax > self.rows) || (cmax > self.cols)
         {
@@ -166,11 +182,6 @@ where T: PartialEq
         }
         true
     }
-
-    fn ne(&self, other: &Mat<T>) -> bool 
-    {
-        !(self == other)
-    }
 }
 
 impl Mat<f64>
@@ -532,29 +543,73 @@ where T: DivAssign + Copy
     }
 }
 
+#[cfg(not(feature = "blas"))]
 impl<T> Mat<T>
 where T: AddAssign + Mul<Output = T> + Copy + Default
 {
     pub fn matmul(&self, rhs: &Mat<T>) -> Mat<T>
     {
         assert!(self.cols == rhs.rows);
+        self._matmul(rhs)
+    }
+
+    pub fn vecmul(&self, rhs: &Vector<T>) -> Vector<T>
+    {
+        assert!(self.cols == rhs.len());
+        self._vecmul(rhs)
+    }
+}
+
+// The BLAS kernels only apply to f64, which is found by downcasting and so
+// needs 'static element types.
+#[cfg(feature = "blas")]
+impl<T> Mat<T>
+where T: AddAssign + Mul<Output = T> + Copy + Default + 'static
+{
+    pub fn matmul(&self, rhs: &Mat<T>) -> Mat<T>
+    {
+        assert!(self.cols == rhs.rows);
+        if let Some(out) = crate::blas::matmul(self, rhs)
+        {
+            return out;
+        }
+        self._matmul(rhs)
+    }
+
+    pub fn vecmul(&self, rhs: &Vector<T>) -> Vector<T>
+    {
+        assert!(self.cols == rhs.len());
+        if let Some(out) = crate::blas::vecmul(self, rhs)
+        {
+            return out;
+        }
+        self._vecmul(rhs)
+    }
+}
+
+impl<T> Mat<T>
+where T: AddAssign + Mul<Output = T> + Copy + Default
+{
+    fn _matmul(&self, rhs: &Mat<T>) -> Mat<T>
+    {
         let mut out = Mat::<T>::new((self.rows, rhs.cols));
+        // ikj order walks rows of rhs and out contiguously.
         for i in 0..self.rows
         {
-            for j in 0..rhs.cols
+            for k in 0..self.cols
             {
-                for k in 0..self.cols
+                let aik = self[(i, k)];
+                for j in 0..rhs.cols
                 {
-                    out[(i, j)] += self[(i, k)] * rhs[(k, j)];
+                    out[(i, j)] += aik * rhs[(k, j)];
                 }
             }
         }
         out
     }
 
-    pub fn vecmul(&self, rhs: &Vector<T>) -> Vector<T>
+    fn _vecmul(&self, rhs: &Vector<T>) -> Vector<T>
     {
-        assert!(self.cols == rhs.len());
         let mut out = Vector::new(self.rows);
         for row in 0..self.rows
         {
@@ -576,11 +631,9 @@ where T: Copy
         assert!((row1 < self.rows) && (row2 < self.rows));        
         for col in 0..self.cols
         {
-            let p1 = (row1, col);
-            let p2 = (row2, col);
-            let temp = self[p1];
-            self[p1] = self[p2];
-            self[p2] = temp;
+            let p1 = row1 * self.row_stride + col * self.col_stride;
+            let p2 = row2 * self.row_stride + col * self.col_stride;
+            self.elements.swap(p1, p2);
         }
     }
 
@@ -599,27 +652,39 @@ impl <T> Mat<T>
         self.elements.iter_mut()
     }
 
+    // Takes ownership of elements laid out with the given strides.
+    pub(crate) fn _from_strided(shape: (usize, usize), row_stride: usize, col_stride: usize, elements: Vec<T>) -> Self
+    {
+        assert!(elements.len() == shape.0 * shape.1);
+        Self { rows: shape.0, cols: shape.1, row_stride, col_stride, elements }
+    }
+
+    pub(crate) fn _is_row_major(&self) -> bool
+    {
+        self.col_stride == 1 && self.row_stride == self.cols && self.elements.len() == self.rows * self.cols
+    }
+
+    pub(crate) fn _is_col_major(&self) -> bool
+    {
+        self.row_stride == 1 && self.col_stride == self.rows && self.elements.len() == self.rows * self.cols
+    }
+
+    pub(crate) fn _elements(&self) -> &[T]
+    {
+        &self.elements
+    }
+
     pub fn transpose_self(mut self) -> Self
     {   
-        let mut temp = self.rows;
-        self.rows = self.cols;
-        self.cols = temp;
-        
-        temp = self.row_stride;
-        self.row_stride = self.col_stride;
-        self.col_stride = temp;
+        std::mem::swap(&mut self.rows, &mut self.cols);
+        std::mem::swap(&mut self.row_stride, &mut self.col_stride);
         self
     } 
     
     pub fn transpose(&mut self)
     {   
-        let mut temp = self.rows;
-        self.rows = self.cols;
-        self.cols = temp;
-        
-        temp = self.row_stride;
-        self.row_stride = self.col_stride;
-        self.col_stride = temp;
+        std::mem::swap(&mut self.rows, &mut self.cols);
+        std::mem::swap(&mut self.row_stride, &mut self.col_stride);
     }
 }
 
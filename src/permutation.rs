@@ -0,0 +1,144 @@
+use crate::matrix::Mat;
+use crate::vector::Vector;
+
+
+// Row i of P·A is row indices[i] of A.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Permutation
+{
+    indices: Vec<usize>,
+}
+
+impl Permutation
+{
+    pub fn identity(size: usize) -> Self
+    {
+        Self { indices: (0..size).collect() }
+    }
+
+    pub fn from_vec(indices: Vec<usize>) -> Self
+    {
+        let mut seen = vec![false; indices.len()];
+        for &i in indices.iter()
+        {
+            assert!(i < indices.len() && !seen[i]);
+            seen[i] = true;
+        }
+        Self { indices }
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.indices.len()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.indices.is_empty()
+    }
+
+    pub fn indices(&self) -> &[usize]
+    {
+        &self.indices
+    }
+
+    pub fn swap(&mut self, i: usize, j: usize)
+    {
+        self.indices.swap(i, j);
+    }
+
+    pub fn inverse(&self) -> Permutation
+    {
+        let mut inv = vec![0; self.len()];
+        for (i, &p) in self.indices.iter().enumerate()
+        {
+            inv[p] = i;
+        }
+        Permutation { indices: inv }
+    }
+
+    // Returns self·other, i.e. applying other first and then self.
+    pub fn compose(&self, other: &Permutation) -> Permutation
+    {
+        assert!(self.len() == other.len());
+        let indices = self.indices.iter().map(|&i| other.indices[i]).collect();
+        Permutation { indices }
+    }
+
+    pub fn sign(&self) -> f64
+    {
+        let n = self.len();
+        let mut visited = vec![false; n];
+        let mut cycles = 0;
+        for start in 0..n
+        {
+            if visited[start]
+            {
+                continue;
+            }
+            cycles += 1;
+            let mut i = start;
+            while !visited[i]
+            {
+                visited[i] = true;
+                i = self.indices[i];
+            }
+        }
+
+        if (n - cycles).is_multiple_of(2) { 1.0 } else { -1.0 }
+    }
+
+    pub fn to_mat(&self) -> Mat<f64>
+    {
+        let n = self.len();
+        let mut mat = Mat::new((n, n));
+        for (i, &p) in self.indices.iter().enumerate()
+        {
+            mat[(i, p)] = 1.0;
+        }
+        mat
+    }
+}
+
+impl Permutation
+{
+    // Returns P·A.
+    pub fn apply_rows<T>(&self, mat: &Mat<T>) -> Mat<T>
+    where T: Default + Clone
+    {
+        assert!(self.len() == mat.rows);
+        let mut out = Mat::new(mat.shape());
+        for (row, &p) in self.indices.iter().enumerate()
+        {
+            for col in 0..mat.cols
+            {
+                out[(row, col)] = mat[(p, col)].clone();
+            }
+        }
+        out
+    }
+
+    // Returns A·Pᵀ, i.e. column j of the result is column indices[j] of A.
+    pub fn apply_cols<T>(&self, mat: &Mat<T>) -> Mat<T>
+    where T: Default + Clone
+    {
+        assert!(self.len() == mat.cols);
+        let mut out = Mat::new(mat.shape());
+        for row in 0..mat.rows
+        {
+            for (col, &p) in self.indices.iter().enumerate()
+            {
+                out[(row, col)] = mat[(row, p)].clone();
+            }
+        }
+        out
+    }
+
+    // Returns P·v.
+    pub fn apply<T>(&self, vec: &Vector<T>) -> Vector<T>
+    where T: Default + Clone
+    {
+        assert!(self.len() == vec.len());
+        Vector::from_vec(self.indices.iter().map(|&p| vec[p].clone()).collect())
+    }
+}
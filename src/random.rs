@@ -0,0 +1,144 @@
+use crate::matrix::Mat;
+use crate::vector::Vector;
+
+
+// xoshiro256** seeded through splitmix64, the same seed always produces the
+// same sequence on every platform.
+#[derive(Clone, Debug)]
+pub struct Rng
+{
+    state: [u64; 4],
+    spare: Option<f64>,
+}
+
+impl Rng
+{
+    pub fn new(seed: u64) -> Rng
+    {
+        let mut x = seed;
+        let mut state = [0u64; 4];
+        for s in state.iter_mut()
+        {
+            x = x.wrapping_add(0x9e3779b97f4a7c15);
+            let mut z = x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            *s = z ^ (z >> 31);
+        }
+        Rng { state, spare: None }
+    }
+
+    pub fn next_u64(&mut self) -> u64
+    {
+        let s = &mut self.state;
+        let out = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        out
+    }
+
+    // Uniform in [0, 1) with 53 random bits.
+    pub fn uniform(&mut self) -> f64
+    {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // Standard normal by the Box-Muller transform, values come in pairs.
+    pub fn normal(&mut self) -> f64
+    {
+        if let Some(z) = self.spare.take()
+        {
+            return z;
+        }
+        let u = 1.0 - self.uniform();
+        let v = self.uniform();
+        let radius = (-2.0 * u.ln()).sqrt();
+        let angle = 2.0 * std::f64::consts::PI * v;
+        self.spare = Some(radius * angle.sin());
+        radius * angle.cos()
+    }
+}
+
+impl Vector<f64>
+{
+    pub fn uniform(len: usize, low: f64, high: f64, rng: &mut Rng) -> Vector<f64>
+    {
+        Vector::from_vec((0..len).map(|_| low + (high - low) * rng.uniform()).collect())
+    }
+
+    pub fn normal(len: usize, rng: &mut Rng) -> Vector<f64>
+    {
+        Vector::from_vec((0..len).map(|_| rng.normal()).collect())
+    }
+}
+
+impl Mat<f64>
+{
+    pub fn uniform(shape: (usize, usize), low: f64, high: f64, rng: &mut Rng) -> Mat<f64>
+    {
+        Mat::from_vec(shape, (0..shape.0 * shape.1).map(|_| low + (high - low) * rng.uniform()).collect())
+    }
+
+    pub fn normal(shape: (usize, usize), rng: &mut Rng) -> Mat<f64>
+    {
+        Mat::from_vec(shape, (0..shape.0 * shape.1).map(|_| rng.normal()).collect())
+    }
+
+    // Haar distributed, the QR of a gaussian matrix with the signs of r
+    // moved into q.
+    pub fn orthogonal(size: usize, rng: &mut Rng) -> Mat<f64>
+    {
+        let qr = Mat::normal((size, size), rng).qr();
+        let (mut q, r) = (qr.q(), qr.r());
+        for c in 0..size
+        {
+            if r[(c, c)] < 0.0
+            {
+                for row in 0..size
+                {
+                    q[(row, c)] = -q[(row, c)];
+                }
+            }
+        }
+        q
+    }
+
+    // Eigenvalues drawn uniformly from [1, size + 1).
+    pub fn spd(size: usize, rng: &mut Rng) -> Mat<f64>
+    {
+        let q = Mat::orthogonal(size, rng);
+        let d = Vector::uniform(size, 1.0, size as f64 + 1.0, rng);
+        q.matmul(&Mat::from_diag(&d)).matmul(&q.transposed())
+    }
+
+    // u diag(singular_values) vᵀ with random orthogonal u and v.
+    pub fn with_singular_values(shape: (usize, usize), singular_values: &Vector<f64>, rng: &mut Rng) -> Mat<f64>
+    {
+        assert!(singular_values.len() == std::cmp::min(shape.0, shape.1));
+        let mut s = Mat::new(shape);
+        for (i, &x) in singular_values.iter().enumerate()
+        {
+            assert!(x >= 0.0, "Singular values must be nonnegative.");
+            s[(i, i)] = x;
+        }
+        let u = Mat::orthogonal(shape.0, rng);
+        let v = Mat::orthogonal(shape.1, rng);
+        u.matmul(&s).matmul(&v.transposed())
+    }
+
+    // Singular values spaced geometrically from 1 down to 1 / condition.
+    pub fn with_condition(shape: (usize, usize), condition: f64, rng: &mut Rng) -> Mat<f64>
+    {
+        assert!(condition >= 1.0);
+        let k = std::cmp::min(shape.0, shape.1);
+        let singular_values = (0..k)
+            .map(|i| if k > 1 { condition.powf(-(i as f64) / (k - 1) as f64) } else { 1.0 })
+            .collect();
+        Mat::with_singular_values(shape, &Vector::from_vec(singular_values), rng)
+    }
+}
@@ -0,0 +1,240 @@
+use std::ops::AddAssign;
+
+use crate::matrix::Mat;
+use crate::vector::Vector;
+
+
+// The axis that is reduced away: Rows gives one value per column, Cols one
+// value per row.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Axis
+{
+    Rows,
+    Cols,
+}
+
+impl<T> Mat<T>
+where T: Copy
+{
+    fn _lanes(&self, axis: Axis) -> usize
+    {
+        match axis
+        {
+            Axis::Rows => self.cols,
+            Axis::Cols => self.rows,
+        }
+    }
+
+    // Elements of lane i in logical order, whatever the strides.
+    fn _lane(&self, axis: Axis, i: usize) -> impl Iterator<Item = T> + '_
+    {
+        let len = match axis { Axis::Rows => self.rows, Axis::Cols => self.cols };
+        (0..len).map(move |j| match axis
+        {
+            Axis::Rows => self[(j, i)],
+            Axis::Cols => self[(i, j)],
+        })
+    }
+
+    fn _all(&self) -> impl Iterator<Item = T> + '_
+    {
+        (0..self.rows).flat_map(move |r| self._lane(Axis::Cols, r))
+    }
+
+    fn _reduce<U: Default + Clone>(&self, axis: Axis, f: impl Fn(&mut dyn Iterator<Item = T>) -> U) -> Vector<U>
+    {
+        Vector::from_vec((0..self._lanes(axis)).map(|i| f(&mut self._lane(axis, i))).collect())
+    }
+}
+
+impl<T> Mat<T>
+where T: Copy + Default + AddAssign
+{
+    pub fn sum(&self) -> T
+    {
+        _sum(&mut self._all())
+    }
+
+    pub fn sum_axis(&self, axis: Axis) -> Vector<T>
+    {
+        self._reduce(axis, _sum)
+    }
+
+    // Running sum in row-major order.
+    pub fn cumsum(&self) -> Vector<T>
+    {
+        let mut acc = T::default();
+        Vector::from_vec(self._all().map(|x| { acc += x; acc }).collect())
+    }
+
+    pub fn cumsum_axis(&self, axis: Axis) -> Mat<T>
+    {
+        let mut out = Mat::new(self.shape());
+        for i in 0..self._lanes(axis)
+        {
+            let mut acc = T::default();
+            for (j, x) in self._lane(axis, i).enumerate()
+            {
+                acc += x;
+                match axis
+                {
+                    Axis::Rows => out[(j, i)] = acc,
+                    Axis::Cols => out[(i, j)] = acc,
+                }
+            }
+        }
+        out
+    }
+}
+
+impl<T> Mat<T>
+where T: Copy + Default + PartialOrd
+{
+    pub fn min(&self) -> T
+    {
+        let (r, c) = self.argmin();
+        self[(r, c)]
+    }
+
+    pub fn max(&self) -> T
+    {
+        let (r, c) = self.argmax();
+        self[(r, c)]
+    }
+
+    // First position of the smallest element.
+    pub fn argmin(&self) -> (usize, usize)
+    {
+        let i = _best(&mut self._all(), |a, b| a < b).0;
+        (i / self.cols, i % self.cols)
+    }
+
+    pub fn argmax(&self) -> (usize, usize)
+    {
+        let i = _best(&mut self._all(), |a, b| a > b).0;
+        (i / self.cols, i % self.cols)
+    }
+
+    pub fn min_axis(&self, axis: Axis) -> Vector<T>
+    {
+        self._reduce(axis, |lane| _best(lane, |a, b| a < b).1)
+    }
+
+    pub fn max_axis(&self, axis: Axis) -> Vector<T>
+    {
+        self._reduce(axis, |lane| _best(lane, |a, b| a > b).1)
+    }
+
+    pub fn argmin_axis(&self, axis: Axis) -> Vector<usize>
+    {
+        self._reduce(axis, |lane| _best(lane, |a, b| a < b).0)
+    }
+
+    pub fn argmax_axis(&self, axis: Axis) -> Vector<usize>
+    {
+        self._reduce(axis, |lane| _best(lane, |a, b| a > b).0)
+    }
+}
+
+impl Mat<f64>
+{
+    pub fn prod(&self) -> f64
+    {
+        self._all().product()
+    }
+
+    pub fn prod_axis(&self, axis: Axis) -> Vector<f64>
+    {
+        self._reduce(axis, |lane| lane.product())
+    }
+
+    pub fn mean(&self) -> f64
+    {
+        assert!(self.rows * self.cols > 0, "Mean of an empty matrix.");
+        self.sum() / (self.rows * self.cols) as f64
+    }
+
+    pub fn mean_axis(&self, axis: Axis) -> Vector<f64>
+    {
+        self._reduce(axis, _mean)
+    }
+
+    // Variance with ddof delta degrees of freedom, 0 for the population
+    // variance and 1 for the sample variance.
+    pub fn var(&self, ddof: usize) -> f64
+    {
+        _var(&mut self._all(), ddof)
+    }
+
+    pub fn var_axis(&self, axis: Axis, ddof: usize) -> Vector<f64>
+    {
+        self._reduce(axis, |lane| _var(lane, ddof))
+    }
+
+    pub fn std(&self, ddof: usize) -> f64
+    {
+        self.var(ddof).sqrt()
+    }
+
+    pub fn std_axis(&self, axis: Axis, ddof: usize) -> Vector<f64>
+    {
+        self._reduce(axis, |lane| _var(lane, ddof).sqrt())
+    }
+}
+
+fn _sum<T: Default + AddAssign>(values: &mut dyn Iterator<Item = T>) -> T
+{
+    let mut acc = T::default();
+    for x in values
+    {
+        acc += x;
+    }
+    acc
+}
+
+// First element that no other one beats along with its index, NaNs are
+// skipped unless there is nothing else.
+fn _best<T: PartialOrd>(values: &mut dyn Iterator<Item = T>, better: impl Fn(&T, &T) -> bool) -> (usize, T)
+{
+    let mut best: Option<(usize, T)> = None;
+    for (i, x) in values.enumerate()
+    {
+        let replace = match &best
+        {
+            None => true,
+            Some((_, b)) => better(&x, b) || (_unordered(b) && !_unordered(&x)),
+        };
+        if replace
+        {
+            best = Some((i, x));
+        }
+    }
+    best.expect("Reduction of an empty lane.")
+}
+
+// NaN is the only value not equal to itself.
+fn _unordered<T: PartialOrd>(x: &T) -> bool
+{
+    x.partial_cmp(x).is_none()
+}
+
+fn _mean(values: &mut dyn Iterator<Item = f64>) -> f64
+{
+    let (mut sum, mut n) = (0.0, 0);
+    for x in values
+    {
+        sum += x;
+        n += 1;
+    }
+    assert!(n > 0, "Mean of an empty lane.");
+    sum / n as f64
+}
+
+// Two pass variance, more accurate than the running sum of squares.
+fn _var(values: &mut dyn Iterator<Item = f64>, ddof: usize) -> f64
+{
+    let values = values.collect::<Vec<f64>>();
+    assert!(values.len() > ddof, "Not enough values for the degrees of freedom.");
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    values.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (values.len() - ddof) as f64
+}
@@ -0,0 +1,189 @@
+use serde::de::Error;
+use serde::ser::{SerializeSeq, SerializeStruct};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::matrix::Mat;
+use crate::vector::Vector;
+use crate::permutation::Permutation;
+use crate::decompositions::lu::LU;
+use crate::decompositions::lup::LUP;
+use crate::decompositions::qr::QR;
+use crate::decompositions::eig::EIG;
+
+
+// Matrices are stored as rows, cols and the elements in row-major order,
+// independent of the strides.
+impl<T: Serialize> Serialize for Mat<T>
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        let mut state = serializer.serialize_struct("Mat", 3)?;
+        state.serialize_field("rows", &self.rows)?;
+        state.serialize_field("cols", &self.cols)?;
+        state.serialize_field("data", &RowMajor(self))?;
+        state.end()
+    }
+}
+
+struct RowMajor<'a, T>(&'a Mat<T>);
+
+impl<T: Serialize> Serialize for RowMajor<'_, T>
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        let mat = self.0;
+        let mut seq = serializer.serialize_seq(Some(mat.rows * mat.cols))?;
+        for r in 0..mat.rows
+        {
+            for c in 0..mat.cols
+            {
+                seq.serialize_element(&mat[(r, c)])?;
+            }
+        }
+        seq.end()
+    }
+}
+
+impl<'de, T: Deserialize<'de> + Default + Clone> Deserialize<'de> for Mat<T>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "Mat")]
+        struct Data<T>
+        {
+            rows: usize,
+            cols: usize,
+            data: Vec<T>,
+        }
+
+        let mat = Data::<T>::deserialize(deserializer)?;
+        if mat.rows.checked_mul(mat.cols) != Some(mat.data.len())
+        {
+            return Err(D::Error::custom(format!("Mat of shape ({}, {}) needs {} elements, found {}.",
+                mat.rows, mat.cols, mat.rows.saturating_mul(mat.cols), mat.data.len())));
+        }
+        Ok(Mat::from_vec((mat.rows, mat.cols), mat.data))
+    }
+}
+
+impl<T: Serialize> Serialize for Vector<T>
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de, T: Deserialize<'de> + Default + Clone> Deserialize<'de> for Vector<T>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        Ok(Vector::from_vec(Vec::<T>::deserialize(deserializer)?))
+    }
+}
+
+impl<'de, T: Deserialize<'de> + Default + Clone> Deserialize<'de> for LU<T>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "LU")]
+        struct Data<T: Default + Clone>
+        {
+            compact: Mat<T>,
+        }
+
+        let lu = Data::<T>::deserialize(deserializer)?;
+        if lu.compact.rows != lu.compact.cols
+        {
+            return Err(D::Error::custom("LU factors must be square."));
+        }
+        Ok(LU { compact: lu.compact })
+    }
+}
+
+impl<'de, T: Deserialize<'de> + Default + Clone> Deserialize<'de> for LUP<T>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "LUP")]
+        struct Data<T: Default + Clone>
+        {
+            compact: Mat<T>,
+            perm: Vec<usize>,
+            num_swaps: usize,
+        }
+
+        let lup = Data::<T>::deserialize(deserializer)?;
+        let n = lup.compact.rows;
+        if lup.compact.cols != n || lup.perm.len() != n
+        {
+            return Err(D::Error::custom("LUP factors must be square and match the permutation length."));
+        }
+        let mut seen = vec![false; n];
+        for &p in lup.perm.iter()
+        {
+            if p >= n || std::mem::replace(&mut seen[p], true)
+            {
+                return Err(D::Error::custom("LUP perm is not a permutation."));
+            }
+        }
+        let even = Permutation::from_vec(lup.perm.clone()).sign() > 0.0;
+        if even != lup.num_swaps.is_multiple_of(2)
+        {
+            return Err(D::Error::custom("LUP num_swaps does not match the parity of perm."));
+        }
+        Ok(LUP { compact: lup.compact, perm: lup.perm, num_swaps: lup.num_swaps })
+    }
+}
+
+impl<'de, T: Deserialize<'de> + Default + Clone> Deserialize<'de> for QR<T>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "QR")]
+        struct Data<T: Default + Clone>
+        {
+            compact: Mat<T>,
+            tau: Vec<T>,
+            explicit_q: Option<Mat<T>>,
+        }
+
+        let qr = Data::<T>::deserialize(deserializer)?;
+        let (m, n) = qr.compact.shape();
+        let valid = match &qr.explicit_q
+        {
+            Some(q) => q.shape() == (m, m) && qr.tau.is_empty(),
+            None => qr.tau.len() == std::cmp::min(m, n),
+        };
+        if !valid
+        {
+            return Err(D::Error::custom("QR reflectors or q do not match the shape of r."));
+        }
+        Ok(QR { compact: qr.compact, tau: qr.tau, explicit_q: qr.explicit_q })
+    }
+}
+
+impl<'de, T: Deserialize<'de> + Default + Clone> Deserialize<'de> for EIG<T>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "EIG")]
+        struct Data<T: Default + Clone>
+        {
+            eigvals: Vector<T>,
+            eigvecs: Mat<T>,
+        }
+
+        let eig = Data::<T>::deserialize(deserializer)?;
+        if eig.eigvecs.cols != eig.eigvals.len()
+        {
+            return Err(D::Error::custom("EIG needs one eigenvector per eigenvalue."));
+        }
+        Ok(EIG { eigvals: eig.eigvals, eigvecs: eig.eigvecs })
+    }
+}
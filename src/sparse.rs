@@ -0,0 +1,65 @@
+use crate::matrix::Mat;
+
+
+// Coordinate format, entries are (row, col, value) triplets in no particular
+// order. Duplicates are summed when converting to dense.
+#[derive(Clone, Debug)]
+pub struct Coo<T>
+{
+    pub rows: usize,
+    pub cols: usize,
+    pub entries: Vec<(usize, usize, T)>,
+}
+
+impl<T> Coo<T>
+{
+    pub fn new(shape: (usize, usize)) -> Self
+    {
+        Coo { rows: shape.0, cols: shape.1, entries: Vec::new() }
+    }
+
+    pub fn shape(&self) -> (usize, usize)
+    {
+        (self.rows, self.cols)
+    }
+
+    pub fn nnz(&self) -> usize
+    {
+        self.entries.len()
+    }
+
+    pub fn push(&mut self, row: usize, col: usize, value: T)
+    {
+        assert!(row < self.rows && col < self.cols);
+        self.entries.push((row, col, value));
+    }
+}
+
+impl Coo<f64>
+{
+    pub fn from_dense(mat: &Mat<f64>) -> Self
+    {
+        let mut coo = Coo::new(mat.shape());
+        for r in 0..mat.rows
+        {
+            for c in 0..mat.cols
+            {
+                if mat[(r, c)] != 0.0
+                {
+                    coo.push(r, c, mat[(r, c)]);
+                }
+            }
+        }
+        coo
+    }
+
+    pub fn to_dense(&self) -> Mat<f64>
+    {
+        let mut mat = Mat::new(self.shape());
+        for &(r, c, v) in self.entries.iter()
+        {
+            mat[(r, c)] += v;
+        }
+        mat
+    }
+}
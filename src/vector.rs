@@ -1,6 +1,16 @@
 use std::ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign};
 
 
+#[macro_export]
+macro_rules! vector {
+    ($item:expr; $len:expr) => (
+        $crate::vector::Vector::from_vec(vec![$item; $len])
+    );
+    ($($item:expr),* $(,)?) => (
+        $crate::vector::Vector::from_vec(vec![$($item),*])
+    );
+}
+
 #[derive(Clone, Debug)]
 pub struct Vector<T>
 {
@@ -27,13 +37,18 @@ impl <T> Vector<T>
     {
         self.elements.len()
     }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.elements.is_empty()
+    }
     
-    pub fn iter(&self) -> std::slice::Iter<T>
+    pub fn iter(&self) -> std::slice::Iter<'_, T>
     {
         self.elements.iter()
     }
     
-    pub fn iter_mut(&mut self) -> std::slice::IterMut<T>
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T>
     {
         self.elements.iter_mut()
     }
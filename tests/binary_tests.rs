@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests
+{
+    use redpill::matrix::Mat;
+    use redpill::vector::Vector;
+    use redpill::sparse::Coo;
+    use redpill::io::ReadError;
+    use redpill::io::binary::{load, save, Mapped};
+
+    fn roundtrip<P: redpill::io::binary::Persist>(object: &P) -> P
+    {
+        let mut bytes = Vec::new();
+        save(&mut bytes, object).unwrap();
+        assert!(bytes.len() % 64 == 0);
+        load(bytes.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn binary_roundtrip_test()
+    {
+        let matrix = Mat::from_vec((3, 3),
+            vec![4.0, 1.0, 2.0, 1.0, 5.0, -1.0, 2.0, -1.0, 6.0,]
+        );
+        assert!(roundtrip(&matrix) == matrix);
+        assert!(roundtrip(&matrix.transposed()) == matrix.transposed());
+        assert!(roundtrip(&Mat::from_vec((2, 2), vec![1i64, -2, 3, 4])) == Mat::from_vec((2, 2), vec![1i64, -2, 3, 4]));
+        let vector = Vector::from_vec(vec![1.5f32, -2.0, 0.25]);
+        assert!(roundtrip(&vector).iter().copied().collect::<Vec<f32>>() == vec![1.5, -2.0, 0.25]);
+
+        let mut coo = Coo::new((3, 4));
+        coo.push(0, 3, 2.0);
+        coo.push(2, 1, -1.0);
+        assert!(roundtrip(&coo).to_dense() == coo.to_dense());
+
+        let b = Vector::from_vec(vec![1.0, -2.0, 3.0]);
+        let x = matrix.clone().lup().solve(&b);
+        assert!(roundtrip(&matrix.clone().lup()).solve(&b).approximately(&x, 1e-15));
+        assert!(roundtrip(&matrix.clone().qr()).solve(&b).approximately(&x, 1e-12));
+        assert!(roundtrip(&matrix.clone().cholesky().unwrap()).solve(&b).approximately(&x, 1e-12));
+        assert!(roundtrip(&matrix.clone().ldl()).solve(&b).approximately(&x, 1e-12));
+        let eig = matrix.clone().eigh();
+        assert!(roundtrip(&eig).eigvecs == eig.eigvecs);
+
+        let mut qr = matrix.clone().qr();
+        qr.delete_row(1);
+        assert!(roundtrip(&qr).r() == qr.r());
+    }
+
+    #[test]
+    fn binary_error_test()
+    {
+        let matrix = Mat::from_vec((2, 2), vec![1.0, 2.0, 3.0, 4.0]);
+        let mut bytes = Vec::new();
+        save(&mut bytes, &matrix).unwrap();
+
+        assert!(matches!(load::<Mat<f32>, _>(bytes.as_slice()), Err(ReadError::Format(_))));
+        assert!(matches!(load::<Vector<f64>, _>(bytes.as_slice()), Err(ReadError::Format(_))));
+
+        let mut corrupted = bytes.clone();
+        corrupted[128] ^= 1;
+        assert!(matches!(load::<Mat<f64>, _>(corrupted.as_slice()), Err(ReadError::Format(_))));
+        let mut corrupted = bytes.clone();
+        corrupted[0] = b'X';
+        assert!(matches!(load::<Mat<f64>, _>(corrupted.as_slice()), Err(ReadError::Format(_))));
+        assert!(matches!(load::<Mat<f64>, _>(&bytes[..150]), Err(ReadError::Format(_))));
+
+        // An array count the file cannot hold is rejected before anything is allocated.
+        let mut crafted = bytes[..64].to_vec();
+        crafted[12..16].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(load::<Mat<f64>, _>(crafted.as_slice()), Err(ReadError::Format(_))));
+    }
+
+    #[test]
+    fn binary_mapped_test()
+    {
+        let matrix = Mat::from_vec((2, 3), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).transposed();
+        let path = std::env::temp_dir().join(format!("redpill_binary_{}.bin", std::process::id()));
+        redpill::io::binary::save_file(&path, &matrix).unwrap();
+
+        let mapped = Mapped::open(&path).unwrap();
+        assert!(mapped.len() == 1);
+        assert!(mapped.slice::<f64>(0).unwrap() == [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert!(mapped.slice::<i64>(0).is_err());
+        let view = mapped.mat::<f64>(0).unwrap();
+        assert!(view.shape() == (3, 2) && view[(0, 1)] == 4.0 && view[(2, 0)] == 3.0);
+        assert!(view.to_mat() == matrix);
+        assert!(mapped.load::<Mat<f64>>().unwrap() == matrix);
+        drop(mapped);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
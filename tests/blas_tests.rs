@@ -0,0 +1,87 @@
+#[cfg(all(test, feature = "blas"))]
+mod tests
+{
+    use redpill::matrix::Mat;
+    use redpill::vector::Vector;
+    use redpill::blas::native;
+
+    fn sample(rows: usize, cols: usize) -> Mat<f64>
+    {
+        let mut mat = Mat::new((rows, cols));
+        for i in 0..rows
+        {
+            for j in 0..cols
+            {
+                mat[(i, j)] = ((i * i + 3 * j * j + i * j) as f64).sin();
+            }
+        }
+        mat
+    }
+
+    #[test]
+    fn blas_matmul_test()
+    {
+        let (a, b) = (sample(7, 5), sample(5, 4));
+        let native_ab = native(|| a.matmul(&b));
+        assert!(a.matmul(&b).approximately(&native_ab, 1e-12));
+        // Column-major storage through transposed matrices.
+        let (at, bt) = (sample(5, 7).transpose_self(), sample(4, 5).transpose_self());
+        assert!(at.matmul(&b).approximately(&native(|| at.matmul(&b)), 1e-12));
+        assert!(a.matmul(&bt).approximately(&native(|| a.matmul(&bt)), 1e-12));
+
+        let x = Vector::from_vec(vec![1.0, -2.0, 0.5, 3.0, -1.0]);
+        assert!(a.vecmul(&x).approximately(&native(|| a.vecmul(&x)), 1e-12));
+        assert!(bt.transposed().vecmul(&Vector::from_vec(vec![1.0, 2.0, 3.0, 4.0]))
+            .approximately(&native(|| bt.transposed().vecmul(&Vector::from_vec(vec![1.0, 2.0, 3.0, 4.0]))), 1e-12));
+    }
+
+    #[test]
+    fn blas_lup_qr_test()
+    {
+        let a = sample(9, 9);
+        let lup = a.clone().lup();
+        let native_lup = native(|| a.clone().lup());
+        assert_eq!(lup.perm, native_lup.perm);
+        assert_eq!(lup.num_swaps, native_lup.num_swaps);
+        assert!(lup.compact.approximately(&native_lup.compact, 1e-10));
+
+        let tall = sample(8, 5);
+        let qr = tall.clone().qr();
+        let native_qr = native(|| tall.clone().qr());
+        assert!(qr.r().approximately(&native_qr.r(), 1e-10));
+        assert!(qr.q().matmul(&qr.r()).approximately(&tall, 1e-10));
+    }
+
+    #[test]
+    fn blas_eigh_test()
+    {
+        let s = sample(6, 6);
+        let sym = s.clone() + s.transposed();
+        let eig = sym.clone().eigh();
+        let native_eig = native(|| sym.clone().eigh());
+        assert!(eig.eigvals.approximately(&native_eig.eigvals, 1e-10));
+        let mut lambda = Mat::new((6, 6));
+        for i in 0..6
+        {
+            lambda[(i, i)] = eig.eigvals[i];
+        }
+        assert!(eig.eigvecs.matmul(&lambda).matmul(&eig.eigvecs.transposed()).approximately(&sym, 1e-10));
+    }
+
+    #[test]
+    fn blas_eig_test()
+    {
+        let a = sample(6, 6);
+        let eig = a.clone().eig(500);
+        assert!(eig.eigvecs.transposed().matmul(&eig.eigvecs).approximately(&Mat::eye(6), 1e-10));
+        let schur = eig.eigvecs.transposed().matmul(&a).matmul(&eig.eigvecs);
+        for i in 0..6
+        {
+            assert!((schur[(i, i)] - eig.eigvals[i]).abs() < 1e-10);
+            for j in 0..i.saturating_sub(1)
+            {
+                assert!(schur[(i, j)].abs() < 1e-10);
+            }
+        }
+    }
+}
@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod tests
+{
+    use redpill::matrix::Mat;
+    use redpill::{mat, vector};
+
+    #[test]
+    fn fill_constructors_test()
+    {
+        assert!(Mat::<f64>::zeros((2, 3)) == mat![0.0, 0.0, 0.0; 0.0, 0.0, 0.0]);
+        assert!(Mat::ones((1, 2)) == mat![1.0, 1.0]);
+        assert!(Mat::full((2, 1), 7) == mat![7; 7]);
+        assert!(Mat::from_fn((2, 3), |r, c| 10 * r + c) == mat![0, 1, 2; 10, 11, 12]);
+        assert!(Mat::from_diag(&vector![1.0, 2.0]) == mat![1.0, 0.0; 0.0, 2.0]);
+
+        let rows = [vector![1, 2, 3], vector![4, 5, 6]];
+        assert!(Mat::from_rows(&rows) == mat![1, 2, 3; 4, 5, 6]);
+        assert!(Mat::from_cols(&rows) == mat![1, 4; 2, 5; 3, 6]);
+    }
+
+    #[test]
+    fn stack_test()
+    {
+        let a = mat![1, 2; 3, 4];
+        let b = mat![5; 6];
+        let c = mat![7, 8, 9];
+        assert!(Mat::hstack(&[&a, &b]) == mat![1, 2, 5; 3, 4, 6]);
+        assert!(Mat::vstack(&[&a.transposed(), &mat![0, 0]]) == mat![1, 3; 2, 4; 0, 0]);
+        assert!(Mat::block_diag(&[&a, &c]) == mat![1, 2, 0, 0, 0; 3, 4, 0, 0, 0; 0, 0, 7, 8, 9]);
+        assert!(Mat::<i32>::hstack(&[]).shape() == (0, 0));
+    }
+
+    #[test]
+    fn structured_test()
+    {
+        let x = vector![1.0, 2.0, 3.0];
+        let y = vector![1.0, 4.0, 5.0, 6.0];
+        assert!(Mat::vandermonde(&x, 3) == mat![1.0, 1.0, 1.0; 1.0, 2.0, 4.0; 1.0, 3.0, 9.0]);
+        assert!(Mat::toeplitz(&x, &y) == mat![1.0, 4.0, 5.0, 6.0; 2.0, 1.0, 4.0, 5.0; 3.0, 2.0, 1.0, 4.0]);
+        assert!(Mat::hankel(&x, &y) == mat![1.0, 2.0, 3.0, 4.0; 2.0, 3.0, 4.0, 5.0; 3.0, 4.0, 5.0, 6.0]);
+        assert!(Mat::circulant(&x) == mat![1.0, 3.0, 2.0; 2.0, 1.0, 3.0; 3.0, 2.0, 1.0]);
+
+        let h = Mat::hilbert(3);
+        assert!(h.approximately(&mat![1.0, 1.0/2.0, 1.0/3.0; 1.0/2.0, 1.0/3.0, 1.0/4.0; 1.0/3.0, 1.0/4.0, 1.0/5.0], 1e-15));
+        assert!(h.cholesky().is_ok());
+    }
+}
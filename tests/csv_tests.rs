@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests
+{
+    use redpill::matrix::Mat;
+    use redpill::io::ReadError;
+    use redpill::io::csv::{CsvOptions, Missing};
+
+    #[test]
+    fn csv_read_test()
+    {
+        let text = "x,y,z\n1, 2.5,-3\r\n\n4,5e-1,\"6\"\n";
+        let options = CsvOptions { skip_lines: 1, ..Default::default() };
+        let mat = Mat::from_csv(text.as_bytes(), &options).unwrap();
+        assert!(mat == Mat::from_vec((2, 3), vec![1.0, 2.5, -3.0, 4.0, 0.5, 6.0]));
+
+        let tabs = "1\t\t3\n4\t5\t6\n";
+        let options = CsvOptions { delimiter: '\t', missing: Missing::Nan, ..Default::default() };
+        let mat = Mat::from_csv(tabs.as_bytes(), &options).unwrap();
+        assert!(mat[(0, 1)].is_nan() && mat[(1, 2)] == 6.0);
+    }
+
+    #[test]
+    fn csv_error_test()
+    {
+        let line_of = |text: &str, options: &CsvOptions| match Mat::from_csv(text.as_bytes(), options)
+        {
+            Err(ReadError::Parse { line, .. }) => line,
+            _ => 0,
+        };
+        let options = CsvOptions::default();
+        assert!(line_of("1,2\n3,,4\n", &options) == 2);
+        assert!(line_of("1,2\n3,4\n5\n", &options) == 3);
+        assert!(line_of("1,2\n\n3,x\n", &options) == 3);
+        assert!(line_of("a,b\n1,2\n", &CsvOptions { skip_lines: 1, ..Default::default() }) == 0);
+        assert!(line_of("1,2\n\"3,4\n", &options) == 2);
+        assert!(line_of("1,2\n\"3\"x,4\n", &options) == 2);
+    }
+
+    #[test]
+    fn csv_quoted_test()
+    {
+        // A delimiter inside quotes belongs to the field.
+        let text = "\"1\", \" 2 \" ,3\n\"4,5\",6\n";
+        let err = Mat::from_csv(text.as_bytes(), &CsvOptions::default()).unwrap_err();
+        assert!(err.to_string() == "line 2: Invalid value '4,5'.");
+        let mat = Mat::from_csv(&text.as_bytes()[..14], &CsvOptions::default()).unwrap();
+        assert!(mat == Mat::from_vec((1, 3), vec![1.0, 2.0, 3.0]));
+
+        let options = CsvOptions { delimiter: ';', skip_lines: 1, ..Default::default() };
+        let text = "\"a;b\";\"say \"\"c\"\"\"\n\"1\";2\n";
+        assert!(Mat::from_csv(text.as_bytes(), &options).unwrap() == Mat::from_vec((1, 2), vec![1.0, 2.0]));
+
+        let mat = Mat::from_vec((1, 2), vec![1.0, 2.0]);
+        let mut buffer = Vec::new();
+        let names = Some(vec!["a;b".to_string(), "say \"c\"".to_string()]);
+        mat.write_csv(&mut buffer, &CsvOptions { column_names: names, ..options.clone() }).unwrap();
+        assert!(String::from_utf8(buffer.clone()).unwrap() == "\"a;b\";\"say \"\"c\"\"\"\n1;2\n");
+        assert!(Mat::from_csv(buffer.as_slice(), &options).unwrap() == mat);
+    }
+
+    #[test]
+    fn csv_write_test()
+    {
+        let mat = Mat::from_vec((2, 2), vec![1.0, 1.0 / 3.0, -2.5, 1e-20]);
+        let mut buffer = Vec::new();
+        mat.write_csv(&mut buffer, &CsvOptions::default()).unwrap();
+        assert!(Mat::from_csv(buffer.as_slice(), &CsvOptions::default()).unwrap() == mat);
+
+        let options = CsvOptions
+        {
+            delimiter: ';',
+            precision: Some(2),
+            column_names: Some(vec!["a".to_string(), "b".to_string()]),
+            ..Default::default()
+        };
+        let mut buffer = Vec::new();
+        mat.write_csv(&mut buffer, &options).unwrap();
+        assert!(String::from_utf8(buffer).unwrap() == "a;b\n1.00;0.33\n-2.50;0.00\n");
+
+        let mut buffer = Vec::new();
+        let options = CsvOptions { column_names: Some(vec!["a".to_string()]), ..Default::default() };
+        let err = mat.write_csv(&mut buffer, &options).unwrap_err();
+        assert!(err.kind() == std::io::ErrorKind::InvalidInput && buffer.is_empty());
+    }
+}
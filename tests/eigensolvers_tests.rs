@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod tests
+{
+    use redpill::matrix::Mat;
+    use redpill::vector::Vector;
+    use redpill::decompositions::eig::EIG;
+    use redpill::eigensolvers::{Which, LinearOperator};
+    use redpill::eigensolvers::krylov::{lanczos, arnoldi};
+    use redpill::eigensolvers::iteration::{power_iteration, inverse_iteration, rayleigh_quotient_iteration};
+
+    fn path_laplacian(n: usize) -> Mat<f64>
+    {
+        let mut lap = Mat::new((n, n));
+        for i in 0..n
+        {
+            lap[(i, i)] = if i == 0 || i == n - 1 { 1.0 } else { 2.0 };
+            if i + 1 < n
+            {
+                lap[(i, i + 1)] = -1.0;
+                lap[(i + 1, i)] = -1.0;
+            }
+        }
+        lap
+    }
+
+    fn assert_eigpairs(matrix: &Mat<f64>, eig: &EIG<f64>, tol: f64)
+    {
+        for j in 0..eig.eigvals.len()
+        {
+            let mut x = Vector::new(matrix.rows);
+            for i in 0..matrix.rows
+            {
+                x[i] = eig.eigvecs[(i, j)];
+            }
+            assert!(matrix.apply(&x).approximately(&(x.clone() * eig.eigvals[j]), tol));
+        }
+    }
+
+    #[test]
+    fn lanczos_largest_test()
+    {
+        let n = 100;
+        let lap = path_laplacian(n);
+        let eig = lanczos(&lap, 4, Which::Largest, 1e-10, 300).unwrap();
+
+        for k in 0..4
+        {
+            let theta = std::f64::consts::PI * (n - 1 - k) as f64 / n as f64;
+            assert!((eig.eigvals[k] - (2.0 - 2.0 * theta.cos())).abs() < 1e-8);
+        }
+        assert_eigpairs(&lap, &eig, 1e-6);
+    }
+
+    #[test]
+    fn lanczos_shift_invert_test()
+    {
+        let n = 100;
+        let lap = path_laplacian(n);
+        let eig = lanczos(&lap, 3, Which::NearestTo(-0.1), 1e-10, 100).unwrap();
+
+        for k in 0..3
+        {
+            let theta = std::f64::consts::PI * k as f64 / n as f64;
+            assert!((eig.eigvals[k] - (2.0 - 2.0 * theta.cos())).abs() < 1e-8);
+        }
+        assert_eigpairs(&lap, &eig, 1e-6);
+    }
+
+    #[test]
+    fn arnoldi_test()
+    {
+        let n = 60;
+        let mut matrix = Mat::new((n, n));
+        for i in 0..n
+        {
+            matrix[(i, i)] = (i + 1) as f64;
+            for j in (i + 1)..n
+            {
+                matrix[(i, j)] = (((i * 7 + j * 3) % 5) as f64 - 2.0) * 0.1;
+            }
+        }
+
+        let eig = arnoldi(&matrix, 3, Which::Largest, 1e-10, 300).unwrap();
+        for k in 0..3
+        {
+            assert!((eig.eigvals[k] - (n - k) as f64).abs() < 1e-8);
+        }
+        assert_eigpairs(&matrix, &eig, 1e-6);
+
+        let eig = arnoldi(&matrix, 2, Which::NearestTo(10.2), 1e-10, 100).unwrap();
+        assert!((eig.eigvals[0] - 10.0).abs() < 1e-8);
+        assert!((eig.eigvals[1] - 11.0).abs() < 1e-8);
+        assert_eigpairs(&matrix, &eig, 1e-6);
+    }
+
+    #[test]
+    fn power_iteration_test()
+    {
+        let matrix = Mat::from_vec((3, 3), 
+            vec![2.0, -1.0, 0.0, -1.0, 2.0, -1.0, 0.0, -1.0, 2.0]
+        );
+        let pair = power_iteration(&matrix, 1e-10, 1000);
+        assert!(pair.residual <= 1e-10);
+        assert!((pair.eigval - (2.0 + 2f64.sqrt())).abs() < 1e-8);
+        assert!(matrix.vecmul(&pair.eigvec).approximately(&(pair.eigvec.clone() * pair.eigval), 1e-8));
+
+        // An unconverged pair still reports the residual of its own vector.
+        let pair = power_iteration(&matrix, 1e-10, 3);
+        assert!(pair.iterations == 3 && pair.residual > 1e-10);
+        let av = matrix.vecmul(&pair.eigvec);
+        assert!(pair.eigval == pair.eigvec.dot(&av));
+        assert!(pair.residual == (av - pair.eigvec.clone() * pair.eigval).mag());
+
+        let nilpotent = Mat::from_vec((2, 2), vec![0.0, 1.0, 0.0, 0.0]);
+        let pair = power_iteration(&nilpotent, 1e-10, 10);
+        assert!(pair.eigval == 0.0 && pair.residual == 0.0 && pair.eigvec.iter().all(|x| x.is_finite()));
+    }
+
+    #[test]
+    fn inverse_iteration_test()
+    {
+        let matrix = Mat::from_vec((3, 3), 
+            vec![2.0, -1.0, 0.0, -1.0, 2.0, -1.0, 0.0, -1.0, 2.0]
+        );
+        let pair = inverse_iteration(&matrix, 2.1, 1e-12, 100);
+        assert!(pair.residual <= 1e-12);
+        assert!((pair.eigval - 2.0).abs() < 1e-10);
+
+        // A shift exactly on an eigenvalue is perturbed instead of giving NaN.
+        let pair = inverse_iteration(&matrix, 2.0, 1e-12, 100);
+        assert!(pair.residual <= 1e-12 && (pair.eigval - 2.0).abs() < 1e-10);
+        let diagonal = Mat::from_vec((3, 3), vec![1.0, 0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 0.0, 3.0]);
+        let pair = inverse_iteration(&diagonal, 3.0, 1e-12, 100);
+        assert!(pair.residual <= 1e-12 && (pair.eigval - 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn rayleigh_quotient_iteration_test()
+    {
+        let matrix = Mat::from_vec((3, 3), 
+            vec![4.0, 1.0, 0.0, 1.0, 3.0, 1.0, 0.0, 1.0, 2.0]
+        );
+        let x0 = Vector::from_vec(vec![1.0, 0.4, 0.1]);
+        let pair = rayleigh_quotient_iteration(&matrix, &x0, 1e-12, 20);
+        assert!(pair.residual <= 1e-12);
+        assert!(pair.iterations <= 5);
+        assert!(matrix.vecmul(&pair.eigvec).approximately(&(pair.eigvec.clone() * pair.eigval), 1e-10));
+    }
+}
@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod tests
+{
+    use redpill::matrix::Mat;
+    use redpill::equations::sylvester::{solve_sylvester, solve_continuous_lyapunov, solve_discrete_lyapunov};
+    use redpill::equations::riccati::{solve_continuous_are, solve_discrete_are, lqr, dlqr};
+
+    fn system() -> (Mat<f64>, Mat<f64>, Mat<f64>, Mat<f64>)
+    {
+        let a = Mat::from_vec((3, 3), 
+            vec![0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 1.0, -2.0, 3.0]
+        );
+        let b = Mat::from_vec((3, 2), 
+            vec![0.0, 1.0, 1.0, 0.0, 0.0, 1.0]
+        );
+        let q = Mat::from_vec((3, 3), 
+            vec![2.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]
+        );
+        let r = Mat::from_vec((2, 2), 
+            vec![1.0, 0.2, 0.2, 2.0]
+        );
+        (a, b, q, r)
+    }
+
+    #[test]
+    fn sylvester_test()
+    {
+        let a = Mat::from_vec((3, 3), 
+            vec![1.0, -3.0, 2.0, 2.0, 1.0, 0.0, 0.0, 1.0, 4.0]
+        );
+        let b = Mat::from_vec((2, 2), 
+            vec![2.0, 1.0, -1.0, 3.0]
+        );
+        let c = Mat::from_vec((3, 2), 
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]
+        );
+
+        let x = solve_sylvester(&a, &b, &c).unwrap();
+        assert!((a.matmul(&x) + x.matmul(&b)).approximately(&c, 1e-10));
+
+        let minus_a = a.clone() * -1.0;
+        assert!(solve_sylvester(&a, &minus_a, &Mat::new((3, 3))).is_err());
+    }
+
+    #[test]
+    fn continuous_lyapunov_test()
+    {
+        let a = Mat::from_vec((3, 3), 
+            vec![-1.0, 2.0, 0.0, -2.0, -1.0, 1.0, 0.0, 0.5, -3.0]
+        );
+        let q = Mat::from_vec((3, 3), 
+            vec![2.0, 1.0, 0.0, 1.0, 3.0, 1.0, 0.0, 1.0, 1.0]
+        );
+
+        let x = solve_continuous_lyapunov(&a, &q).unwrap();
+        assert!((a.matmul(&x) + x.matmul(&a.transposed())).approximately(&q, 1e-10));
+        assert!(x.approximately(&x.transposed(), 1e-10));
+    }
+
+    #[test]
+    fn discrete_lyapunov_test()
+    {
+        let a = Mat::from_vec((3, 3), 
+            vec![0.5, 0.4, 0.0, -0.6, 0.3, 0.1, 0.0, 0.2, -0.7]
+        );
+        let q = Mat::from_vec((3, 3), 
+            vec![2.0, 1.0, 0.0, 1.0, 3.0, 1.0, 0.0, 1.0, 1.0]
+        );
+
+        let x = solve_discrete_lyapunov(&a, &q).unwrap();
+        let residual = a.matmul(&x).matmul(&a.transposed()) - x.clone() + q;
+        assert!(residual.approximately(&Mat::new((3, 3)), 1e-10));
+
+        let rotation = Mat::from_vec((2, 2), 
+            vec![0.0, -1.0, 1.0, 0.0]
+        );
+        assert!(solve_discrete_lyapunov(&rotation, &Mat::eye(2)).is_err());
+    }
+
+    #[test]
+    fn continuous_are_test()
+    {
+        let one = Mat::from_vec((1, 1), vec![1.0]);
+        let x = solve_continuous_are(&one, &one, &one, &one).unwrap();
+        assert!((x[(0, 0)] - (1.0 + 2f64.sqrt())).abs() < 1e-10);
+
+        let (a, b, q, r) = system();
+        let x = solve_continuous_are(&a, &b, &q, &r).unwrap();
+        let rinv = r.clone().lup().inv();
+        let residual = a.transposed().matmul(&x) + x.matmul(&a)
+            - x.matmul(&b).matmul(&rinv).matmul(&b.transposed()).matmul(&x) + q.clone();
+        assert!(residual.approximately(&Mat::new((3, 3)), 1e-8));
+
+        // The closed loop a - b k is stable.
+        let k = lqr(&a, &b, &q, &r).unwrap();
+        let (_, _, stable) = (a - b.matmul(&k)).ordered_schur(|re, _| re < 0.0).unwrap();
+        assert_eq!(stable, 3);
+    }
+
+    #[test]
+    fn discrete_are_test()
+    {
+        let (a, b, q, r) = system();
+        let a = a * 0.5;
+        let x = solve_discrete_are(&a, &b, &q, &r).unwrap();
+        let at = a.transposed();
+        let bt = b.transposed();
+        let gram = (r.clone() + bt.matmul(&x).matmul(&b)).lup().inv();
+        let residual = at.matmul(&x).matmul(&a) - x.clone()
+            - at.matmul(&x).matmul(&b).matmul(&gram).matmul(&bt).matmul(&x).matmul(&a) + q.clone();
+        assert!(residual.approximately(&Mat::new((3, 3)), 1e-8));
+
+        let k = dlqr(&a, &b, &q, &r).unwrap();
+        let (_, _, stable) = (a - b.matmul(&k)).ordered_schur(|re, im| re * re + im * im < 1.0).unwrap();
+        assert_eq!(stable, 3);
+    }
+
+    #[test]
+    fn riccati_singular_test()
+    {
+        // A singular a makes the symplectic pencil singular.
+        let a = Mat::from_vec((2, 2), vec![0.0, 1.0, 0.0, 0.0]);
+        let b = Mat::from_vec((2, 1), vec![0.0, 1.0]);
+        let q = Mat::eye(2);
+        let r = Mat::eye(1);
+        let x = solve_discrete_are(&a, &b, &q, &r).unwrap();
+        let at = a.transposed();
+        let bt = b.transposed();
+        let gram = (r.clone() + bt.matmul(&x).matmul(&b)).lup().inv();
+        let residual = at.matmul(&x).matmul(&a) - x.clone()
+            - at.matmul(&x).matmul(&b).matmul(&gram).matmul(&bt).matmul(&x).matmul(&a) + q.clone();
+        assert!(residual.approximately(&Mat::new((2, 2)), 1e-8));
+
+        let (a, b, q, _) = system();
+        let r = Mat::from_vec((2, 2), vec![1.0, 2.0, 2.0, 4.0]);
+        assert!(solve_continuous_are(&a, &b, &q, &r).is_err());
+        assert!(solve_discrete_are(&a, &b, &q, &r).is_err());
+        assert!(lqr(&a, &b, &q, &r).is_err());
+    }
+}
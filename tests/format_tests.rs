@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests
+{
+    use redpill::matrix::Mat;
+    use redpill::vector::Vector;
+
+    #[test]
+    fn display_test()
+    {
+        let matrix = Mat::from_vec((2, 3), vec![1.0, -2.5, 3.0, 10.0, 0.25, -6.0]);
+        assert_eq!(format!("{}", matrix), "[[ 1, -2.5,  3],\n [10, 0.25, -6]]");
+        assert_eq!(format!("{:.2}", matrix), "[[ 1.00, -2.50,  3.00],\n [10.00,  0.25, -6.00]]");
+        assert_eq!(format!("{:.1e}", matrix), "[[1.0e0, -2.5e0,  3.0e0],\n [1.0e1, 2.5e-1, -6.0e0]]");
+        assert_eq!(format!("{}", Mat::<f64>::new((0, 3))), "[]");
+
+        let vector = Vector::from_vec(vec![1.0, -0.5]);
+        assert_eq!(format!("{:+.1}", vector), "[+1.0, -0.5]");
+    }
+
+    #[test]
+    fn display_transposed_test()
+    {
+        let matrix = Mat::from_vec((2, 3), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(format!("{}", matrix.transposed()), "[[1, 4],\n [2, 5],\n [3, 6]]");
+        let sub = matrix.transposed().submat(1, 3, 0, 2).unwrap();
+        assert_eq!(format!("{}", sub), "[[2, 5],\n [3, 6]]");
+    }
+
+    #[test]
+    fn display_elision_test()
+    {
+        let matrix = Mat::from_vec((12, 11), (0..132).collect());
+        let expected = "\
+[[  0,   1,   2, ...,   8,   9,  10],
+ [ 11,  12,  13, ...,  19,  20,  21],
+ [ 22,  23,  24, ...,  30,  31,  32],
+ [...],
+ [ 99, 100, 101, ..., 107, 108, 109],
+ [110, 111, 112, ..., 118, 119, 120],
+ [121, 122, 123, ..., 129, 130, 131]]";
+        assert_eq!(format!("{}", matrix), expected);
+
+        let vector = Vector::from_vec((0..20).collect::<Vec<i32>>());
+        assert_eq!(format!("{}", vector), "[0, 1, 2, ..., 17, 18, 19]");
+    }
+}
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod tests
+{
+    use redpill::matrix::Mat;
+    
+    #[test]
+    fn expm_test()
+    {
+        let nilpotent = Mat::from_vec((2, 2), 
+            vec![0.0, 1.0, 0.0, 0.0]
+        );
+        let exp_true = Mat::from_vec((2, 2), 
+            vec![1.0, 1.0, 0.0, 1.0]
+        );
+        assert!(nilpotent.expm().approximately(&exp_true, 1e-12));
+
+        let t: f64 = 10.0;
+        let rotation = Mat::from_vec((2, 2), 
+            vec![0.0, -t, t, 0.0]
+        );
+        let exp_true = Mat::from_vec((2, 2), 
+            vec![t.cos(), -t.sin(), t.sin(), t.cos()]
+        );
+        assert!(rotation.expm().approximately(&exp_true, 1e-10));
+    }
+
+    #[test]
+    fn sqrtm_test()
+    {
+        let matrix = Mat::from_vec((3, 3), 
+            vec![4.0, 1.0, 0.0, 1.0, 3.0, 1.0, 0.0, 1.0, 2.0]
+        );
+        let root = matrix.sqrtm().unwrap();
+        assert!(root.matmul(&root).approximately(&matrix, 1e-10));
+
+        let singular = Mat::from_vec((2, 2), 
+            vec![1.0, 2.0, 2.0, 4.0]
+        );
+        assert!(singular.sqrtm().is_err());
+        let negative = Mat::from_vec((2, 2), 
+            vec![-1.0, 0.0, 0.0, 4.0]
+        );
+        assert!(negative.sqrtm().is_err() && negative.logm().is_err());
+        assert!(negative.powmf(0.5).is_err() && negative.powmf(2.0).is_ok());
+    }
+
+    #[test]
+    fn logm_test()
+    {
+        let matrix = Mat::from_vec((3, 3), 
+            vec![0.5, 1.0, 0.0, -1.0, 0.2, 0.3, 0.0, 0.4, -0.6]
+        );
+        assert!(matrix.expm().logm().unwrap().approximately(&matrix, 1e-10));
+    }
+
+    #[test]
+    fn powm_test()
+    {
+        let matrix = Mat::from_vec((3, 3), 
+            vec![1.0, 1.0, 1.0, 4.0, 3.0, -1.0, 3.0, 5.0, 3.0,]
+        );
+        let cube = matrix.matmul(&matrix).matmul(&matrix);
+        assert!(matrix.powm(3).unwrap().approximately(&cube, 1e-8));
+        assert!(matrix.powm(0).unwrap().approximately(&Mat::eye(3), 1e-8));
+        assert!(matrix.powm(-1).unwrap().matmul(&matrix).approximately(&Mat::eye(3), 1e-8));
+
+        let spd = Mat::from_vec((2, 2), 
+            vec![2.0, 1.0, 1.0, 2.0]
+        );
+        assert!(spd.powmf(0.5).unwrap().approximately(&spd.sqrtm().unwrap(), 1e-10));
+        assert!(spd.powmf(1.5).unwrap().approximately(&spd.sqrtm().unwrap().powm(3).unwrap(), 1e-10));
+
+        let singular = Mat::from_vec((2, 2), vec![1.0, 2.0, 2.0, 4.0]);
+        assert!(singular.powm(2).is_ok());
+        assert!(singular.powm(-1).is_err() && singular.powmf(-1.0).is_err());
+    }
+}
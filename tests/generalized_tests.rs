@@ -0,0 +1,188 @@
+#[cfg(test)]
+mod tests
+{
+    use redpill::matrix::Mat;
+    use redpill::vector::Vector;
+    use redpill::decompositions::generalized::{generalized_eigh, generalized_eig};
+
+    #[test]
+    fn cholesky_test()
+    {
+        let matrix = Mat::from_vec((3, 3), 
+            vec![4.0, 12.0, -16.0, 12.0, 37.0, -43.0, -16.0, -43.0, 98.0]
+        );
+        let l_true = Mat::from_vec((3, 3), 
+            vec![2.0, 0.0, 0.0, 6.0, 1.0, 0.0, -8.0, 5.0, 3.0]
+        );
+
+        let chol = matrix.clone().cholesky().unwrap();
+        assert!(chol.l.approximately(&l_true, 1e-8));
+        assert!((chol.det() - 36.0).abs() < 1e-8);
+
+        let b = Vector::from_vec(vec![1.0, 2.0, 3.0]);
+        assert!(matrix.vecmul(&chol.solve(&b)).approximately(&b, 1e-8));
+        assert!(matrix.matmul(&chol.inv()).approximately(&Mat::eye(3), 1e-8));
+
+        let indefinite = Mat::from_vec((2, 2), vec![1.0, 2.0, 2.0, 1.0]);
+        assert!(indefinite.cholesky().is_err());
+    }
+
+    #[test]
+    fn eigh_test()
+    {
+        let matrix = Mat::from_vec((3, 3), 
+            vec![2.0, -1.0, 0.0, -1.0, 2.0, -1.0, 0.0, -1.0, 2.0]
+        );
+        let eig = matrix.clone().eigh();
+        let eigvals_true = Vector::from_vec(vec![2.0 - 2f64.sqrt(), 2.0, 2.0 + 2f64.sqrt()]);
+        assert!(eig.eigvals.approximately(&eigvals_true, 1e-10));
+
+        let mut diag = Mat::new((3, 3));
+        for i in 0..3
+        {
+            diag[(i, i)] = eig.eigvals[i];
+        }
+        let rebuilt = eig.eigvecs.matmul(&diag).matmul(&eig.eigvecs.transposed());
+        assert!(rebuilt.approximately(&matrix, 1e-10));
+
+        let with_nan = Mat::from_vec((2, 2), vec![1.0, f64::NAN, f64::NAN, 2.0]);
+        assert!(with_nan.eigh().eigvals.len() == 2);
+    }
+
+    #[test]
+    fn generalized_eigh_test()
+    {
+        let stiffness = Mat::from_vec((3, 3), 
+            vec![6.0, -2.0, 0.0, -2.0, 4.0, -2.0, 0.0, -2.0, 2.0]
+        );
+        let mass = Mat::from_vec((3, 3), 
+            vec![2.0, 0.5, 0.0, 0.5, 2.0, 0.5, 0.0, 0.5, 1.0]
+        );
+
+        let eig = generalized_eigh(&stiffness, &mass).unwrap();
+        let x = &eig.eigvecs;
+        assert!(x.transposed().matmul(&mass).matmul(x).approximately(&Mat::eye(3), 1e-10));
+        for j in 0..3
+        {
+            let mut kx = stiffness.matmul(x);
+            let mx = mass.matmul(x);
+            for i in 0..3
+            {
+                kx[(i, j)] -= eig.eigvals[j] * mx[(i, j)];
+                assert!(kx[(i, j)].abs() < 1e-10);
+            }
+        }
+        assert!(eig.eigvals[0] <= eig.eigvals[1] && eig.eigvals[1] <= eig.eigvals[2]);
+    }
+
+    #[test]
+    fn qz_test()
+    {
+        let a = Mat::from_vec((4, 4), 
+            vec![1.0, 2.0, 3.0, 4.0, -2.0, 1.0, 0.5, 2.0, 3.0, -1.0, 2.0, 1.0, 0.0, 1.0, -3.0, 2.0]
+        );
+        let b = Mat::from_vec((4, 4), 
+            vec![2.0, 1.0, 0.0, 0.5, 0.0, 3.0, 1.0, 0.0, 1.0, 0.0, 2.0, 1.0, 0.5, 0.0, 0.0, 1.0]
+        );
+
+        let qz = a.clone().qz(b.clone()).unwrap();
+        let zt = qz.z.transposed();
+        assert!(qz.q.matmul(&qz.s).matmul(&zt).approximately(&a, 1e-10));
+        assert!(qz.q.matmul(&qz.t).matmul(&zt).approximately(&b, 1e-10));
+        assert!(qz.q.transposed().matmul(&qz.q).approximately(&Mat::eye(4), 1e-10));
+        assert!(qz.z.transposed().matmul(&qz.z).approximately(&Mat::eye(4), 1e-10));
+        for i in 0..4
+        {
+            for j in 0..i
+            {
+                assert!(qz.t[(i, j)] == 0.0);
+                if j + 1 < i
+                {
+                    assert!(qz.s[(i, j)] == 0.0);
+                }
+            }
+        }
+
+        // Compare with the eigenvalues of b^-1 a.
+        let c = b.lup().inv().matmul(&a);
+        for i in 0..4
+        {
+            let (re, im) = (qz.eigvals_re[i], qz.eigvals_im[i]);
+            // det(c - λ I) for complex λ, via the real 8x8 embedding.
+            let mut embed = Mat::new((8, 8));
+            for r in 0..4
+            {
+                for k in 0..4
+                {
+                    let v = c[(r, k)] - if r == k { re } else { 0.0 };
+                    embed[(r, k)] = v;
+                    embed[(r + 4, k + 4)] = v;
+                }
+                embed[(r, r + 4)] = im;
+                embed[(r + 4, r)] = -im;
+            }
+            assert!(embed.lup().det().abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn qz_singular_test()
+    {
+        let a = Mat::from_vec((3, 3), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 10.0]);
+        let b = Mat::from_vec((3, 3), vec![1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0]);
+
+        let qz = a.clone().qz(b.clone()).unwrap();
+        let zt = qz.z.transposed();
+        assert!(qz.q.matmul(&qz.s).matmul(&zt).approximately(&a, 1e-10));
+        assert!(qz.q.matmul(&qz.t).matmul(&zt).approximately(&b, 1e-10));
+        assert!(qz.eigvals_re.iter().filter(|e| e.is_infinite()).count() == 1);
+
+        // b x = 0 for the infinite eigenvalue, a x = λ b x for the finite ones.
+        let eig = generalized_eig(&a, &b).unwrap();
+        for j in 0..3
+        {
+            let lambda = eig.eigvals_re[j];
+            assert!(eig.eigvals_im[j] == 0.0);
+            let x = Vector::from_vec((0..3).map(|r| eig.eigvecs[(r, j)]).collect());
+            let residual = if lambda.is_infinite() { b.vecmul(&x) } else { a.vecmul(&x) - b.vecmul(&x) * lambda };
+            assert!(residual.iter().all(|r| r.abs() < 1e-10));
+        }
+
+        let nan = Mat::from_vec((3, 3), vec![f64::NAN; 9]);
+        assert!(nan.qz(b).is_err());
+    }
+
+    #[test]
+    fn generalized_eig_test()
+    {
+        let a = Mat::from_vec((4, 4), 
+            vec![1.0, 2.0, 3.0, 4.0, -2.0, 1.0, 0.5, 2.0, 3.0, -1.0, 2.0, 1.0, 0.0, 1.0, -3.0, 2.0]
+        );
+        let b = Mat::from_vec((4, 4), 
+            vec![2.0, 1.0, 0.0, 0.5, 0.0, 3.0, 1.0, 0.0, 1.0, 0.0, 2.0, 1.0, 0.5, 0.0, 0.0, 1.0]
+        );
+
+        let eig = generalized_eig(&a, &b).unwrap();
+        let column = |j: usize| Vector::from_vec((0..4).map(|r| eig.eigvecs[(r, j)]).collect());
+        let mut j = 0;
+        while j < 4
+        {
+            let (re, im) = (eig.eigvals_re[j], eig.eigvals_im[j]);
+            let u = column(j);
+            if im == 0.0
+            {
+                assert!((a.vecmul(&u) - b.vecmul(&u) * re).iter().all(|r| r.abs() < 1e-10));
+                j += 1;
+            }
+            else
+            {
+                // a (u + i v) = (re + i im) b (u + i v).
+                let v = column(j + 1);
+                let (bu, bv) = (b.vecmul(&u), b.vecmul(&v));
+                assert!((a.vecmul(&u) - bu.clone() * re + bv.clone() * im).iter().all(|r| r.abs() < 1e-10));
+                assert!((a.vecmul(&v) - bv * re - bu * im).iter().all(|r| r.abs() < 1e-10));
+                j += 2;
+            }
+        }
+    }
+}
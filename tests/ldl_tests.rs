@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests
+{
+    use redpill::matrix::Mat;
+    use redpill::vector::Vector;
+
+    fn kkt() -> Mat<f64>
+    {
+        // [[h, aᵀ], [a, 0]] with h positive definite and a of full row rank.
+        Mat::from_vec((5, 5), 
+            vec![4.0, 1.0, 0.0, 1.0, 0.0,
+                 1.0, 3.0, 1.0, 1.0, 1.0,
+                 0.0, 1.0, 2.0, 0.0, 2.0,
+                 1.0, 1.0, 0.0, 0.0, 0.0,
+                 0.0, 1.0, 2.0, 0.0, 0.0]
+        )
+    }
+
+    #[test]
+    fn ldl_test()
+    {
+        let matrix = kkt();
+        let ldl = matrix.clone().ldl();
+        let p = ldl.p();
+        let pap = p.apply_cols(&p.apply_rows(&matrix));
+        assert!(pap.approximately(&ldl.l.matmul(&ldl.d).matmul(&ldl.l.transposed()), 1e-10));
+
+        let zero_diag = Mat::from_vec((2, 2), 
+            vec![0.0, 1.0, 1.0, 0.0]
+        );
+        let ldl = zero_diag.clone().ldl();
+        assert!(ldl.d.approximately(&zero_diag, 1e-12));
+    }
+
+    #[test]
+    fn ldl_solve_test()
+    {
+        let matrix = kkt();
+        let b = Vector::from_vec(vec![1.0, -2.0, 3.0, 0.5, 1.0]);
+        let x = matrix.clone().ldl().solve(&b);
+        assert!(matrix.vecmul(&x).approximately(&b, 1e-10));
+    }
+
+    #[test]
+    fn ldl_det_test()
+    {
+        let matrix = kkt();
+        let det = matrix.clone().lup().det();
+        assert!((matrix.ldl().det() - det).abs() < 1e-10);
+    }
+
+    #[test]
+    fn ldl_inertia_test()
+    {
+        assert_eq!(kkt().ldl().inertia(), (3, 2, 0));
+
+        let singular = Mat::from_vec((3, 3), 
+            vec![1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 3.0, 6.0, 1.0]
+        );
+        let (positive, negative, zero) = singular.ldl().inertia();
+        assert_eq!((positive + negative, zero), (2, 1));
+    }
+}
@@ -57,5 +57,29 @@ mod tests
 
         assert!(((matrix.lup().det() - 49.0).abs() < 1e-8));
     }
+
+    #[test]
+    fn lup_blocked_test()
+    {
+        let n = 23;
+        let mut matrix = Mat::new((n, n));
+        for i in 0..n
+        {
+            for j in 0..n
+            {
+                matrix[(i, j)] = ((i * i + 3 * j * j + i * j) as f64).sin();
+            }
+        }
+
+        let unblocked = matrix.clone().lup_blocked(n);
+        for block in [1, 4, 8]
+        {
+            let lup = matrix.clone().lup_blocked(block);
+            assert_eq!(lup.perm, unblocked.perm);
+            assert_eq!(lup.num_swaps, unblocked.num_swaps);
+            assert!(lup.compact.approximately(&unblocked.compact, 1e-10));
+            assert!(lup.p().apply_rows(&matrix).approximately(&lup.l().matmul(&lup.u()), 1e-10));
+        }
+    }
 }
 
@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests
+{
+    use redpill::matrix::Mat;
+
+    fn rank_two() -> Mat<f64>
+    {
+        // Third row is the sum of the first two, fourth column the difference
+        // of the first two.
+        Mat::from_vec((3, 4), 
+            vec![1.0, 2.0, 3.0, -1.0,
+                 4.0, 5.0, 6.0, -1.0,
+                 5.0, 7.0, 9.0, -2.0]
+        )
+    }
+
+    #[test]
+    fn lu_full_pivot_test()
+    {
+        let matrix = Mat::from_vec((3, 3), 
+            vec![2.0, 1.0, 1.0,
+                 4.0, -6.0, 0.0,
+                 -2.0, 7.0, 2.0]
+        );
+        let lupq = matrix.clone().lu_full_pivot();
+        let paq = lupq.q().apply_cols(&lupq.p().apply_rows(&matrix));
+        assert!(paq.approximately(&lupq.l().matmul(&lupq.u()), 1e-12));
+        assert!((lupq.det() - matrix.clone().lup().det()).abs() < 1e-10);
+        assert!(lupq.rank(1e-12) == 3);
+        assert!(lupq.kernel().cols == 0);
+
+        let lupq = rank_two().lu_full_pivot();
+        let paq = lupq.q().apply_cols(&lupq.p().apply_rows(&rank_two()));
+        assert!(paq.approximately(&lupq.l().matmul(&lupq.u()), 1e-12));
+    }
+
+    #[test]
+    fn lu_full_pivot_rank_test()
+    {
+        let lupq = rank_two().lu_full_pivot();
+        assert!(lupq.rank(1e-10) == 2);
+
+        let nearly_singular = Mat::from_vec((2, 2), 
+            vec![1.0, 1.0,
+                 1.0, 1.0 + 1e-9]
+        );
+        let lupq = nearly_singular.lu_full_pivot();
+        assert!(lupq.rank(1e-12) == 2);
+        assert!(lupq.rank(1e-6) == 1);
+    }
+
+    #[test]
+    fn lu_full_pivot_kernel_image_test()
+    {
+        let matrix = rank_two();
+        let lupq = matrix.clone().lu_full_pivot();
+
+        let kernel = lupq.kernel();
+        assert!(kernel.shape() == (4, 2));
+        assert!(matrix.matmul(&kernel).approximately(&Mat::new((3, 2)), 1e-12));
+        assert!(kernel.lu_full_pivot().rank(1e-10) == 2);
+
+        let image = lupq.image();
+        assert!(image.shape() == (3, 2));
+        assert!(image.clone().lu_full_pivot().rank(1e-10) == 2);
+        // Every column of the matrix lies in the span of the image.
+        for c in 0..matrix.cols
+        {
+            let mut stacked = Mat::new((3, 3));
+            for r in 0..3
+            {
+                stacked[(r, 0)] = image[(r, 0)];
+                stacked[(r, 1)] = image[(r, 1)];
+                stacked[(r, 2)] = matrix[(r, c)];
+            }
+            assert!(stacked.lu_full_pivot().rank(1e-10) == 2);
+        }
+    }
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests
+{
+    use std::io::ErrorKind;
+    use redpill::matrix::Mat;
+    use redpill::sparse::Coo;
+    use redpill::io::ReadError;
+    use redpill::io::matrix_market::{read_dense, read_sparse, write_dense, write_sparse, Field, Symmetry};
+
+    #[test]
+    fn matrix_market_read_test()
+    {
+        let coordinate = "%%MatrixMarket matrix coordinate real general\n\
+                          % comment\n\
+                          3 3 4\n\
+                          1 1 1.5\n\
+                          2 3 -2\n\
+                          3 1 4e-1\n\
+                          3 3 7\n";
+        let dense = read_dense(coordinate.as_bytes()).unwrap();
+        let expected = Mat::from_vec((3, 3), 
+            vec![1.5, 0.0, 0.0, 0.0, 0.0, -2.0, 0.4, 0.0, 7.0]
+        );
+        assert!(dense.approximately(&expected, 1e-15));
+        assert!(read_sparse(coordinate.as_bytes()).unwrap().nnz() == 4);
+
+        let symmetric = "%%MatrixMarket matrix array integer symmetric\n2 2\n1\n2\n3\n";
+        let expected = Mat::from_vec((2, 2), vec![1.0, 2.0, 2.0, 3.0]);
+        assert!(read_dense(symmetric.as_bytes()).unwrap().approximately(&expected, 0.0));
+
+        let skew = "%%MatrixMarket matrix coordinate pattern skew-symmetric\n3 3 2\n2 1\n3 2\n";
+        let expected = Mat::from_vec((3, 3), vec![0.0, -1.0, 0.0, 1.0, 0.0, -1.0, 0.0, 1.0, 0.0]);
+        let sparse = read_sparse(skew.as_bytes()).unwrap();
+        assert!(sparse.nnz() == 4);
+        assert!(sparse.to_dense().approximately(&expected, 0.0));
+    }
+
+    #[test]
+    fn matrix_market_roundtrip_test()
+    {
+        let matrix = Mat::from_vec((2, 3), 
+            vec![1.0, -0.1, 0.0, 1e-300, 3.25, 6.0]
+        );
+        let mut buffer = Vec::new();
+        write_dense(&mut buffer, &matrix, Field::Real, Symmetry::General).unwrap();
+        assert!(read_dense(buffer.as_slice()).unwrap() == matrix);
+
+        let mut coo = Coo::new((3, 3));
+        coo.push(0, 0, 2.0);
+        coo.push(2, 0, -1.0);
+        coo.push(0, 2, -1.0);
+        let mut buffer = Vec::new();
+        write_sparse(&mut buffer, &coo, Field::Integer, Symmetry::Symmetric).unwrap();
+        let text = String::from_utf8(buffer.clone()).unwrap();
+        assert!(text.starts_with("%%MatrixMarket matrix coordinate integer symmetric\n3 3 2\n"));
+        assert!(read_dense(buffer.as_slice()).unwrap() == coo.to_dense());
+    }
+
+    #[test]
+    fn matrix_market_error_test()
+    {
+        let line_of = |text: &str| match read_dense(text.as_bytes())
+        {
+            Err(ReadError::Parse { line, .. }) => line,
+            _ => 0,
+        };
+        assert!(line_of("%%MatrixMarket matrix coordinate complex general\n1 1 1\n1 1 1 0\n") == 1);
+        assert!(line_of("%%MatrixMarket matrix coordinate real general\n2 2 2\n1 1 1.0\n% gap\n3 1 1.0\n") == 5);
+        assert!(line_of("%%MatrixMarket matrix array integer general\n1 2\n1\n2.5\n") == 4);
+        assert!(line_of("%%MatrixMarket matrix coordinate real symmetric\n2 2 1\n1 2 1.0\n") == 3);
+        assert!(line_of("%%MatrixMarket matrix coordinate real general\n2 2 2\n1 1 1.0\n") == 4);
+        assert!(line_of("%%MatrixMarket matrix array real general\n1 1\n1.0\n2.0\n") == 4);
+
+        let err = read_dense("%%MatrixMarket matrix array real general\n1 x\n".as_bytes()).unwrap_err();
+        assert!(err.to_string() == "line 2: Invalid size 'x'.");
+    }
+
+    #[test]
+    fn matrix_market_write_error_test()
+    {
+        let invalid = |result: std::io::Result<()>| result.unwrap_err().kind() == ErrorKind::InvalidInput;
+        let mut buffer = Vec::new();
+        let unsymmetric = Mat::from_vec((2, 2), vec![1.0, 2.0, 3.0, 4.0]);
+        assert!(invalid(write_dense(&mut buffer, &unsymmetric, Field::Real, Symmetry::Symmetric)));
+        assert!(invalid(write_dense(&mut buffer, &unsymmetric, Field::Pattern, Symmetry::General)));
+        let skew = Mat::from_vec((2, 2), vec![1.0, 2.0, -2.0, 0.0]);
+        assert!(invalid(write_dense(&mut buffer, &skew, Field::Real, Symmetry::SkewSymmetric)));
+        assert!(invalid(write_dense(&mut buffer, &Mat::new((2, 3)), Field::Real, Symmetry::Symmetric)));
+        assert!(invalid(write_dense(&mut buffer, &(unsymmetric.clone() * 0.5), Field::Integer, Symmetry::General)));
+        assert!(buffer.is_empty());
+
+        let mut coo = Coo::new((3, 3));
+        coo.push(2, 0, -1.0);
+        coo.push(0, 2, 1.0);
+        assert!(invalid(write_sparse(&mut buffer, &coo, Field::Real, Symmetry::Symmetric)));
+        assert!(invalid(write_sparse(&mut buffer, &coo, Field::Pattern, Symmetry::SkewSymmetric)));
+        write_sparse(&mut buffer, &coo, Field::Real, Symmetry::SkewSymmetric).unwrap();
+        assert!(read_dense(buffer.as_slice()).unwrap() == coo.to_dense());
+    }
+}
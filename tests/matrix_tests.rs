@@ -2,7 +2,8 @@
 mod tests
 {
     use redpill::matrix::Mat;
-    use redpill::colvecs;
+    use redpill::vector::Vector;
+    use redpill::{colvecs, mat, vector};
     
     #[test]
     fn mat_test_macro()
@@ -19,4 +20,23 @@ mod tests
         
         assert!(a.approximately(&b, 1e-8));
     }
+
+    #[test]
+    fn mat_literal_macro_test()
+    {
+        let a = mat![1.0, 2.0, 3.0; 4.0, 5.0, 6.0];
+        assert!(a.shape() == (2, 3));
+        assert!(a == Mat::from_vec((2, 3), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]));
+        assert!(mat![1, 2,; 3, 4,;] == Mat::from_vec((2, 2), vec![1, 2, 3, 4]));
+        assert!(mat![7; 8; 9].shape() == (3, 1));
+        assert!(mat![7, 8, 9].shape() == (1, 3));
+        let empty: Mat<f64> = mat![];
+        assert!(empty.shape() == (0, 0));
+
+        let v = vector![1.0, 2.0, 3.0,];
+        assert!(v.approximately(&Vector::from_vec(vec![1.0, 2.0, 3.0]), 0.0));
+        assert!(vector![0.5; 4].len() == 4);
+        let empty: Vector<f64> = vector![];
+        assert!(empty.is_empty());
+    }
 }
\ No newline at end of file
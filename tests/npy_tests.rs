@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests
+{
+    use redpill::matrix::Mat;
+    use redpill::vector::Vector;
+    use redpill::io::ReadError;
+    use redpill::io::npy::{read_mat, read_vector, write_mat, write_vector, Npz, NpzWriter};
+
+    // Header of a .npy version 1.0 file as written by NumPy.
+    fn npy_header(descr: &str, fortran_order: bool, shape: &str) -> Vec<u8>
+    {
+        let mut header = format!("{{'descr': '{}', 'fortran_order': {}, 'shape': {}, }}",
+            descr, if fortran_order { "True" } else { "False" }, shape);
+        while (header.len() + 11) % 64 != 0
+        {
+            header.push(' ');
+        }
+        header.push('\n');
+        let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+        bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(header.as_bytes());
+        bytes
+    }
+
+    #[test]
+    fn npy_read_test()
+    {
+        let mut bytes = npy_header("<f8", true, "(2, 3)");
+        for v in [1.0f64, 4.0, 2.0, 5.0, 3.0, 6.0]
+        {
+            bytes.extend_from_slice(&v.to_le_bytes());
+        }
+        let mat = read_mat::<f64, _>(bytes.as_slice()).unwrap();
+        assert!(mat == Mat::from_vec((2, 3), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]));
+
+        let mut bytes = npy_header(">i8", false, "(3,)");
+        for v in [7i64, -1, 2]
+        {
+            bytes.extend_from_slice(&v.to_be_bytes());
+        }
+        let vector = read_vector::<i64, _>(bytes.as_slice()).unwrap();
+        assert!(vector.iter().copied().collect::<Vec<i64>>() == vec![7, -1, 2]);
+
+        assert!(matches!(read_mat::<f32, _>(bytes.as_slice()), Err(ReadError::Format(_))));
+        assert!(matches!(read_mat::<i64, _>(bytes.as_slice()), Err(ReadError::Format(_))));
+        assert!(matches!(read_vector::<f64, _>(npy_header("<f8", false, "(2,)").as_slice()), Err(ReadError::Io(_))));
+    }
+
+    #[test]
+    fn npy_bogus_size_test()
+    {
+        // Sizes come from the header and must not be trusted before the data is there.
+        let huge = npy_header("<f8", false, "(1000000000, 1000000000)");
+        assert!(matches!(read_mat::<f64, _>(huge.as_slice()), Err(ReadError::Io(_))));
+        let overflow = npy_header("<f8", false, "(18446744073709551615, 2)");
+        assert!(matches!(read_mat::<f64, _>(overflow.as_slice()), Err(ReadError::Format(_))));
+
+        let mut long_header = b"\x93NUMPY\x02\x00".to_vec();
+        long_header.extend_from_slice(&u32::MAX.to_le_bytes());
+        long_header.extend_from_slice(b"{'descr': '<f8'");
+        assert!(matches!(read_vector::<f64, _>(long_header.as_slice()), Err(ReadError::Io(_))));
+    }
+
+    #[test]
+    fn npy_roundtrip_test()
+    {
+        let mat = Mat::from_vec((2, 3), vec![1.5f32, -2.0, 0.0, 4.0, 1e-3, 6.0]);
+        for m in [mat.clone(), mat.transposed()]
+        {
+            let mut bytes = Vec::new();
+            write_mat(&mut bytes, &m).unwrap();
+            assert!(bytes.len() % 64 == 24);
+            assert!(read_mat::<f32, _>(bytes.as_slice()).unwrap() == m);
+        }
+
+        let vector = Vector::from_vec(vec![0.1f64, 0.2, 0.3]);
+        let mut bytes = Vec::new();
+        write_vector(&mut bytes, &vector).unwrap();
+        assert!(read_vector::<f64, _>(bytes.as_slice()).unwrap().approximately(&vector, 0.0));
+    }
+
+    #[test]
+    fn npz_roundtrip_test()
+    {
+        let mat = Mat::from_vec((2, 2), vec![1.0, 2.0, 3.0, 4.0]);
+        let ints = Mat::from_vec((1, 3), vec![1i64, 2, 3]);
+        let vector = Vector::from_vec(vec![5.0, 6.0]);
+
+        let mut writer = NpzWriter::new(Vec::new());
+        writer.add_mat("a", &mat).unwrap();
+        writer.add_mat("ints", &ints).unwrap();
+        writer.add_vector("v", &vector).unwrap();
+        let bytes = writer.finish().unwrap();
+
+        let npz = Npz::read(bytes.as_slice()).unwrap();
+        assert!(npz.names() == vec!["a", "ints", "v"]);
+        assert!(npz.mat::<f64>("a").unwrap() == mat);
+        assert!(npz.mat::<i64>("ints").unwrap() == ints);
+        assert!(npz.vector::<f64>("v").unwrap().approximately(&vector, 0.0));
+        assert!(npz.mat::<f64>("v").is_err());
+        assert!(npz.mat::<f64>("missing").is_err());
+    }
+}
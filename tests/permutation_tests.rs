@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod tests
+{
+    use redpill::matrix::Mat;
+    use redpill::vector::Vector;
+    use redpill::permutation::Permutation;
+
+    #[test]
+    fn permutation_apply_test()
+    {
+        let matrix = Mat::from_vec((3, 2), 
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]
+        );
+        let p = Permutation::from_vec(vec![2, 0, 1]);
+
+        let rows_true = Mat::from_vec((3, 2), 
+            vec![5.0, 6.0, 1.0, 2.0, 3.0, 4.0]
+        );
+        assert!(p.apply_rows(&matrix).approximately(&rows_true, 1e-8));
+        assert!(p.to_mat().matmul(&matrix).approximately(&rows_true, 1e-8));
+
+        let matrix = matrix.transposed();
+        let cols_true = rows_true.transposed();
+        assert!(p.apply_cols(&matrix).approximately(&cols_true, 1e-8));
+        assert!(matrix.matmul(&p.to_mat().transposed()).approximately(&cols_true, 1e-8));
+
+        let v = Vector::from_vec(vec![1.0, 2.0, 3.0]);
+        let v_true = Vector::from_vec(vec![3.0, 1.0, 2.0]);
+        assert!(p.apply(&v).approximately(&v_true, 1e-8));
+    }
+
+    #[test]
+    fn permutation_inverse_compose_test()
+    {
+        let p = Permutation::from_vec(vec![2, 0, 3, 1]);
+        let q = Permutation::from_vec(vec![1, 3, 0, 2]);
+
+        assert_eq!(p.compose(&p.inverse()), Permutation::identity(4));
+        assert_eq!(p.inverse().compose(&p), Permutation::identity(4));
+        assert!(p.compose(&q).to_mat().approximately(&p.to_mat().matmul(&q.to_mat()), 1e-8));
+    }
+
+    #[test]
+    fn permutation_sign_test()
+    {
+        assert_eq!(Permutation::identity(3).sign(), 1.0);
+        assert_eq!(Permutation::from_vec(vec![1, 0, 2]).sign(), -1.0);
+        assert_eq!(Permutation::from_vec(vec![1, 2, 0]).sign(), 1.0);
+        assert_eq!(Permutation::from_vec(vec![3, 2, 1, 0]).sign(), 1.0);
+    }
+
+    #[test]
+    fn lup_p_test()
+    {
+        let matrix = Mat::from_vec((3, 3), 
+            vec![0.0, 5.0, 22.0/3.0, 4.0, 2.0, 1.0, 2.0, 7.0, 9.0,]
+        );
+        
+        let lup = matrix.clone().lup();
+        let p = lup.p();
+        assert!(p.apply_rows(&matrix).approximately(&lup.l().matmul(&lup.u()), 1e-8));
+        assert_eq!(p.sign() * lup.u().iter_trace().product::<f64>(), lup.det());
+    }
+}
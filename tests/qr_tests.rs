@@ -13,7 +13,7 @@ mod tests
         
         
         let qr = matrix.clone().qr();
-        assert!(matrix.approximately(&qr.q.matmul(&qr.r), 1e-8));
+        assert!(matrix.approximately(&qr.q().matmul(&qr.r()), 1e-8));
     }
 
 
@@ -41,6 +41,74 @@ mod tests
         assert!(((matrix.qr().det() - 49.0).abs() < 1e-8));
     }
     
+    #[test]
+    fn thin_qr_test()
+    {
+        let matrix = Mat::from_vec((4, 2), 
+            vec![1.0, 2.0, 3.0, -1.0, 0.0, 4.0, 2.0, 2.0,]
+        );
+
+        let (q, r) = matrix.clone().thin_qr();
+        assert!(q.shape() == (4, 2) && r.shape() == (2, 2));
+        assert!(r[(1, 0)] == 0.0);
+        assert!(q.transposed().matmul(&q).approximately(&Mat::eye(2), 1e-12));
+        assert!(matrix.approximately(&q.matmul(&r), 1e-12));
+
+        let lq = matrix.transposed().thin_lq();
+        assert!(lq.l.shape() == (2, 2) && lq.q.shape() == (2, 4));
+        assert!(matrix.transposed().approximately(&lq.l.matmul(&lq.q), 1e-12));
+    }
+
+    #[test]
+    fn lq_ql_rq_test()
+    {
+        let matrix = Mat::from_vec((3, 4), 
+            vec![2.0, -1.0, 0.0, 3.0, 1.0, 4.0, -2.0, 1.0, 0.0, 1.0, 5.0, -3.0,]
+        );
+
+        for a in [matrix.clone(), matrix.transposed()]
+        {
+            let (m, n) = a.shape();
+            let lq = a.clone().lq();
+            let ql = a.clone().ql();
+            let rq = a.clone().rq();
+            assert!(a.approximately(&lq.l.matmul(&lq.q), 1e-12));
+            assert!(a.approximately(&ql.q.matmul(&ql.l), 1e-12));
+            assert!(a.approximately(&rq.r.matmul(&rq.q), 1e-12));
+            assert!(lq.q.matmul(&lq.q.transposed()).approximately(&Mat::eye(n), 1e-12));
+            assert!(ql.q.matmul(&ql.q.transposed()).approximately(&Mat::eye(m), 1e-12));
+            assert!(rq.q.matmul(&rq.q.transposed()).approximately(&Mat::eye(n), 1e-12));
+
+            for r in 0..m
+            {
+                for c in 0..n
+                {
+                    // l is lower trapezoidal, ql fills the bottom right corner
+                    // and rq the top right one.
+                    if c > r { assert!(lq.l[(r, c)].abs() < 1e-12); }
+                    if c + m > r + n { assert!(ql.l[(r, c)].abs() < 1e-12); }
+                    if c + m < r + n { assert!(rq.r[(r, c)].abs() < 1e-12); }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn qr_apply_q_test()
+    {
+        let matrix = Mat::from_vec((4, 3), 
+            vec![1.0, 2.0, 0.0, -1.0, 3.0, 2.0, 4.0, 0.0, 1.0, 2.0, -2.0, 5.0,]
+        );
+        let qr = matrix.clone().qr();
+        let q = qr.q();
+        assert!(q.transposed().matmul(&q).approximately(&Mat::eye(4), 1e-12));
+        assert!(qr.apply_q(&qr.r()).approximately(&matrix, 1e-12));
+        assert!(qr.apply_qt(&matrix).approximately(&qr.r(), 1e-12));
+
+        let b = Vector::from_vec(vec![1.0, -2.0, 0.5, 3.0]);
+        assert!(qr.apply_q_vec(&b).approximately(&q.vecmul(&b), 1e-12));
+        assert!(qr.apply_qt_vec(&qr.apply_q_vec(&b)).approximately(&b, 1e-12));
+    }
 }
 
 
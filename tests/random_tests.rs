@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod tests
+{
+    use redpill::matrix::Mat;
+    use redpill::vector::Vector;
+    use redpill::random::Rng;
+
+    #[test]
+    fn rng_test()
+    {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let x = Mat::uniform((4, 5), -1.0, 2.0, &mut a);
+        assert!(x == Mat::uniform((4, 5), -1.0, 2.0, &mut b));
+        assert!(x.iter().all(|&v| (-1.0..2.0).contains(&v)));
+        assert!(Rng::new(43).next_u64() != Rng::new(42).next_u64());
+
+        let n = 20000;
+        let z = Vector::normal(n, &mut a);
+        let mean = z.sum() / n as f64;
+        let var = z.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / n as f64;
+        assert!(mean.abs() < 0.05 && (var - 1.0).abs() < 0.05);
+        let u = Vector::uniform(n, 0.0, 1.0, &mut a);
+        assert!((u.sum() / n as f64 - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn structured_random_test()
+    {
+        let mut rng = Rng::new(7);
+        let q = Mat::orthogonal(30, &mut rng);
+        assert!(q.transposed().matmul(&q).approximately(&Mat::eye(30), 1e-12));
+
+        let a = Mat::spd(30, &mut rng);
+        assert!(a.approximately(&a.transposed(), 1e-12));
+        assert!(a.clone().cholesky().is_ok());
+        assert!(a.eigh().eigvals.iter().all(|&e| (1.0 - 1e-10..31.0 + 1e-10).contains(&e)));
+
+        // Singular values are the square roots of the eigenvalues of aᵀa.
+        let a = Mat::with_condition((40, 25), 1e6, &mut rng);
+        let eigvals = a.transposed().matmul(&a).eigh().eigvals;
+        let (smin, smax) = (eigvals[0].sqrt(), eigvals[24].sqrt());
+        assert!((smax - 1.0).abs() < 1e-10 && (smin - 1e-6).abs() < 1e-10);
+    }
+
+    #[test]
+    fn random_solve_test()
+    {
+        let mut rng = Rng::new(2024);
+        for n in [10, 50, 120]
+        {
+            let a = Mat::with_condition((n, n), 1e3, &mut rng);
+            let x = Vector::normal(n, &mut rng);
+            let b = a.vecmul(&x);
+            assert!(a.clone().lup().solve(&b).approximately(&x, 1e-10));
+            assert!(a.clone().qr().solve(&b).approximately(&x, 1e-10));
+            assert!(a.lu_full_pivot().rank(1e-10) == n);
+        }
+    }
+}
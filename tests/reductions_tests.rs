@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod tests
+{
+    use redpill::matrix::Mat;
+    use redpill::vector::Vector;
+    use redpill::reductions::Axis;
+    use redpill::{mat, vector};
+
+    fn as_vec<T: Copy>(v: &Vector<T>) -> Vec<T>
+    {
+        v.iter().copied().collect()
+    }
+
+    #[test]
+    fn reductions_test()
+    {
+        let a = mat![1.0, -2.0, 3.0; 4.0, 5.0, -6.0];
+        assert!(a.sum() == 5.0 && a.prod() == 720.0);
+        assert!(a.min() == -6.0 && a.max() == 5.0);
+        assert!(a.argmin() == (1, 2) && a.argmax() == (1, 1));
+        assert!((a.mean() - 5.0 / 6.0).abs() < 1e-15);
+        assert!((a.var(0) - 14.472222222222221).abs() < 1e-12);
+        assert!((a.std(1) - (a.var(0) * 6.0 / 5.0).sqrt()).abs() < 1e-12);
+        assert!(as_vec(&a.cumsum()) == vec![1.0, -1.0, 2.0, 6.0, 11.0, 5.0]);
+
+        let b = mat![3, 1, 3; 0, 7, 0];
+        assert!(b.sum() == 14 && b.argmax() == (1, 1) && b.argmin() == (1, 0));
+    }
+
+    #[test]
+    fn axis_reductions_test()
+    {
+        let a = mat![1.0, -2.0, 3.0; 4.0, 5.0, -6.0];
+        assert!(as_vec(&a.sum_axis(Axis::Rows)) == vec![5.0, 3.0, -3.0]);
+        assert!(as_vec(&a.sum_axis(Axis::Cols)) == vec![2.0, 3.0]);
+        assert!(as_vec(&a.prod_axis(Axis::Cols)) == vec![-6.0, -120.0]);
+        assert!(as_vec(&a.mean_axis(Axis::Rows)) == vec![2.5, 1.5, -1.5]);
+        assert!(as_vec(&a.min_axis(Axis::Rows)) == vec![1.0, -2.0, -6.0]);
+        assert!(as_vec(&a.max_axis(Axis::Cols)) == vec![3.0, 5.0]);
+        assert!(as_vec(&a.argmin_axis(Axis::Cols)) == vec![1, 2]);
+        assert!(as_vec(&a.argmax_axis(Axis::Rows)) == vec![1, 1, 0]);
+        assert!(as_vec(&a.var_axis(Axis::Rows, 0)) == vec![2.25, 12.25, 20.25]);
+        assert!(a.std_axis(Axis::Rows, 1).approximately(&vector![4.5f64.sqrt(), 24.5f64.sqrt(), 40.5f64.sqrt()], 1e-12));
+        assert!(a.cumsum_axis(Axis::Rows) == mat![1.0, -2.0, 3.0; 5.0, 3.0, -3.0]);
+        assert!(a.cumsum_axis(Axis::Cols) == mat![1.0, -1.0, 2.0; 4.0, 9.0, 3.0]);
+    }
+
+    #[test]
+    fn strided_reductions_test()
+    {
+        let a = mat![1.0, -2.0, 3.0; 4.0, 5.0, -6.0];
+        let t = a.transposed();
+        assert!(as_vec(&t.sum_axis(Axis::Cols)) == as_vec(&a.sum_axis(Axis::Rows)));
+        assert!(as_vec(&t.cumsum()) == vec![1.0, 5.0, 3.0, 8.0, 11.0, 5.0]);
+        assert!(t.argmin() == (2, 1) && t.argmax() == (1, 1));
+        assert!(t.cumsum_axis(Axis::Rows) == a.cumsum_axis(Axis::Cols).transposed());
+
+        let s = t.submat(1, 3, 0, 2).unwrap();
+        assert!(s.shape() == (2, 2) && s.sum() == 0.0 && s.min() == -6.0);
+
+        let with_nan = Mat::from_vec((1, 3), vec![f64::NAN, 2.0, 1.0]);
+        assert!(with_nan.min() == 1.0 && with_nan.argmax() == (0, 1));
+    }
+}
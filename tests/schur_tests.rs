@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests
+{
+    use redpill::matrix::Mat;
+
+    fn orthogonal(q: &Mat<f64>) -> bool
+    {
+        q.transposed().matmul(q).approximately(&Mat::eye(q.rows), 1e-10)
+    }
+
+    #[test]
+    fn hessenberg_test()
+    {
+        let matrix = Mat::from_vec((4, 4), 
+            vec![4.0, 1.0, -2.0, 2.0, 1.0, 2.0, 0.0, 1.0, -2.0, 0.0, 3.0, -2.0, 2.0, 1.0, -2.0, -1.0]
+        );
+
+        let (q, h) = matrix.clone().hessenberg();
+        assert!(orthogonal(&q));
+        assert!(q.matmul(&h).matmul(&q.transposed()).approximately(&matrix, 1e-10));
+        for i in 2..4
+        {
+            for j in 0..(i - 1)
+            {
+                assert!(h[(i, j)] == 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn schur_test()
+    {
+        let matrix = Mat::from_vec((4, 4), 
+            vec![1.0, -3.0, 2.0, 0.5, 2.0, 1.0, 0.0, 1.0, 0.0, 1.0, 4.0, -1.0, 1.0, 0.0, 2.0, 3.0]
+        );
+
+        let (z, t) = matrix.clone().schur().unwrap();
+        assert!(orthogonal(&z));
+        assert!(z.matmul(&t).matmul(&z.transposed()).approximately(&matrix, 1e-10));
+
+        // Quasi upper triangular, without two consecutive nonzero subdiagonals.
+        let mut blocks = 0;
+        for i in 1..4
+        {
+            for j in 0..(i - 1)
+            {
+                assert!(t[(i, j)] == 0.0);
+            }
+            if t[(i, i - 1)] != 0.0
+            {
+                blocks += 1;
+                assert!(i == 1 || t[(i - 1, i - 2)] == 0.0);
+            }
+        }
+        assert!(blocks >= 1);
+
+        let trace: f64 = matrix.iter_trace().sum();
+        assert!((t.iter_trace().sum::<f64>() - trace).abs() < 1e-10);
+    }
+
+    #[test]
+    fn ordered_schur_test()
+    {
+        let matrix = Mat::from_vec((4, 4), 
+            vec![1.0, -3.0, 2.0, 0.5, 2.0, 1.0, 0.0, 1.0, 0.0, 1.0, 4.0, -1.0, 1.0, 0.0, 2.0, 3.0]
+        );
+
+        // Two complex pairs, move the one with the larger real part first.
+        let (_, t) = matrix.clone().schur().unwrap();
+        let trailing = t[(2, 2)] + t[(3, 3)];
+        assert!(trailing > 4.0);
+
+        let (z, t, selected) = matrix.clone().ordered_schur(|re, _| re > 2.0).unwrap();
+        assert!(orthogonal(&z));
+        assert!(z.matmul(&t).matmul(&z.transposed()).approximately(&matrix, 1e-10));
+        assert_eq!(selected, 2);
+        assert!(t[(1, 0)] != 0.0 && t[(2, 1)] == 0.0 && t[(3, 2)] != 0.0);
+        assert!((t[(0, 0)] + t[(1, 1)] - trailing).abs() < 1e-10);
+    }
+
+    #[test]
+    fn schur_not_converged_test()
+    {
+        let mut matrix = Mat::eye(3);
+        matrix[(1, 0)] = f64::NAN;
+        assert!(matrix.clone().schur().is_err());
+        assert!(redpill::eigensolvers::krylov::arnoldi(&matrix, 1, redpill::eigensolvers::Which::Largest, 1e-10, 10).is_err());
+    }
+}
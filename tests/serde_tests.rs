@@ -0,0 +1,66 @@
+#[cfg(all(test, feature = "serde"))]
+mod tests
+{
+    use redpill::matrix::Mat;
+    use redpill::vector::Vector;
+    use redpill::decompositions::lup::LUP;
+    use redpill::decompositions::qr::QR;
+    use redpill::decompositions::eig::EIG;
+
+    #[test]
+    fn serde_mat_vector_test()
+    {
+        let mat = Mat::from_vec((2, 3), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let json = serde_json::to_string(&mat).unwrap();
+        assert!(json == r#"{"rows":2,"cols":3,"data":[1.0,2.0,3.0,4.0,5.0,6.0]}"#);
+        assert!(serde_json::from_str::<Mat<f64>>(&json).unwrap() == mat);
+
+        // Transposed matrices are written in row-major order as well.
+        let json = serde_json::to_string(&mat.transposed()).unwrap();
+        assert!(json == r#"{"rows":3,"cols":2,"data":[1.0,4.0,2.0,5.0,3.0,6.0]}"#);
+
+        assert!(serde_json::from_str::<Mat<f64>>(r#"{"rows":2,"cols":2,"data":[1.0]}"#).is_err());
+
+        let vector = Vector::from_vec(vec![1i64, -2, 3]);
+        let json = serde_json::to_string(&vector).unwrap();
+        assert!(json == "[1,-2,3]");
+        let back = serde_json::from_str::<Vector<i64>>(&json).unwrap();
+        assert!(back.iter().copied().collect::<Vec<i64>>() == vec![1, -2, 3]);
+    }
+
+    #[test]
+    fn serde_decompositions_test()
+    {
+        let matrix = Mat::from_vec((3, 3), 
+            vec![0.0, 5.0, 22.0/3.0, 4.0, 2.0, 1.0, 2.0, 7.0, 9.0,]
+        );
+        let lup = matrix.clone().lup();
+        let back = serde_json::from_str::<LUP<f64>>(&serde_json::to_string(&lup).unwrap()).unwrap();
+        assert!(back.compact.approximately(&lup.compact, 1e-15) && back.perm == lup.perm);
+        assert!((back.det() - lup.det()).abs() < 1e-12);
+
+        let mut qr = matrix.clone().qr();
+        let back = serde_json::from_str::<QR<f64>>(&serde_json::to_string(&qr).unwrap()).unwrap();
+        assert!(back.q().matmul(&back.r()).approximately(&matrix, 1e-12));
+        qr.delete_col(0);
+        let back = serde_json::from_str::<QR<f64>>(&serde_json::to_string(&qr).unwrap()).unwrap();
+        assert!(back.q().approximately(&qr.q(), 1e-15));
+
+        let eig = matrix.clone().eigh();
+        let back = serde_json::from_str::<EIG<f64>>(&serde_json::to_string(&eig).unwrap()).unwrap();
+        assert!(back.eigvecs.approximately(&eig.eigvecs, 1e-15));
+    }
+
+    #[test]
+    fn serde_validation_test()
+    {
+        let bad_perm = r#"{"compact":{"rows":2,"cols":2,"data":[1.0,0.0,0.0,1.0]},"perm":[0,0],"num_swaps":0}"#;
+        assert!(serde_json::from_str::<LUP<f64>>(bad_perm).is_err());
+        let bad_parity = r#"{"compact":{"rows":2,"cols":2,"data":[1.0,0.0,0.0,1.0]},"perm":[1,0],"num_swaps":0}"#;
+        assert!(serde_json::from_str::<LUP<f64>>(bad_parity).is_err());
+        let bad_tau = r#"{"compact":{"rows":2,"cols":2,"data":[1.0,0.0,0.0,1.0]},"tau":[0.0],"explicit_q":null}"#;
+        assert!(serde_json::from_str::<QR<f64>>(bad_tau).is_err());
+        let bad_eig = r#"{"eigvals":[1.0,2.0],"eigvecs":{"rows":2,"cols":1,"data":[1.0,0.0]}}"#;
+        assert!(serde_json::from_str::<EIG<f64>>(bad_eig).is_err());
+    }
+}
@@ -0,0 +1,152 @@
+#[cfg(test)]
+mod tests
+{
+    use redpill::matrix::Mat;
+    use redpill::vector::Vector;
+    use redpill::decompositions::givens::Givens;
+    use redpill::decompositions::qr::QR;
+
+    fn design() -> Mat<f64>
+    {
+        Mat::from_vec((5, 3), 
+            vec![1.0, 2.0, 0.0,
+                 -1.0, 3.0, 2.0,
+                 4.0, 0.0, 1.0,
+                 2.0, -2.0, 5.0,
+                 0.5, 1.0, -1.0]
+        )
+    }
+
+    fn check(qr: &QR<f64>, a: &Mat<f64>)
+    {
+        let (q, r) = (qr.q(), qr.r());
+        let m = a.rows;
+        assert!(q.transposed().matmul(&q).approximately(&Mat::eye(m), 1e-12));
+        assert!(q.matmul(&r).approximately(a, 1e-12));
+        for i in 0..m
+        {
+            for j in 0..std::cmp::min(i, a.cols)
+            {
+                assert!(r[(i, j)].abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn givens_test()
+    {
+        let g = Givens::new(3.0, 4.0);
+        let mut v = Vector::from_vec(vec![3.0, 4.0]);
+        g.rotate_vec(&mut v, 0, 1);
+        assert!(v.approximately(&Vector::from_vec(vec![5.0, 0.0]), 1e-12));
+
+        let mut m = Mat::from_vec((2, 2), vec![3.0, 1.0, 4.0, 2.0]);
+        g.rotate_rows(&mut m, 0, 1, 0);
+        assert!(m[(1, 0)].abs() < 1e-12 && (m[(0, 0)] - 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn qr_update_test()
+    {
+        let a = design();
+        let u = Vector::from_vec(vec![1.0, -1.0, 2.0, 0.0, 3.0]);
+        let v = Vector::from_vec(vec![0.5, 2.0, -1.0]);
+        let mut qr = a.clone().qr();
+        qr.update(&u, &v);
+
+        let mut updated = a.clone();
+        for i in 0..5
+        {
+            for j in 0..3
+            {
+                updated[(i, j)] += u[i] * v[j];
+            }
+        }
+        check(&qr, &updated);
+
+        let b = Vector::from_vec(vec![1.0, 2.0, 3.0]);
+        let square = Mat::from_vec((3, 3), vec![2.0, -3.0, 1.0, 2.0, 0.0, -1.0, 1.0, 4.0, 5.0]);
+        let mut qr = square.clone().qr();
+        qr.update(&b, &b);
+        let updated = square + Mat::from_vec((3, 3), vec![1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 3.0, 6.0, 9.0]);
+        assert!(updated.vecmul(&qr.solve(&b)).approximately(&b, 1e-10));
+        assert!((qr.det() - updated.lup().det()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn qr_insert_delete_test()
+    {
+        let a = design();
+        let row = Vector::from_vec(vec![7.0, -1.0, 3.0]);
+        let col = Vector::from_vec(vec![1.0, 0.0, -2.0, 4.0, 1.0]);
+
+        let mut qr = a.clone().qr();
+        qr.insert_row(2, &row);
+        let mut inserted = Mat::new((6, 3));
+        for i in 0..6
+        {
+            for j in 0..3
+            {
+                inserted[(i, j)] = match i { 0 | 1 => a[(i, j)], 2 => row[j], _ => a[(i - 1, j)] };
+            }
+        }
+        check(&qr, &inserted);
+
+        qr.delete_row(2);
+        check(&qr, &a);
+        qr.delete_row(0);
+        check(&qr, &a.submat(1, 5, 0, 3).unwrap());
+
+        let mut qr = a.clone().qr();
+        qr.insert_col(1, &col);
+        let mut inserted = Mat::new((5, 4));
+        for i in 0..5
+        {
+            for j in 0..4
+            {
+                inserted[(i, j)] = match j { 0 => a[(i, 0)], 1 => col[i], _ => a[(i, j - 1)] };
+            }
+        }
+        check(&qr, &inserted);
+
+        qr.delete_col(0);
+        let mut deleted = Mat::new((5, 3));
+        for i in 0..5
+        {
+            for j in 0..3
+            {
+                deleted[(i, j)] = inserted[(i, j + 1)];
+            }
+        }
+        check(&qr, &deleted);
+    }
+
+    #[test]
+    fn cholesky_update_test()
+    {
+        let a = Mat::from_vec((3, 3), 
+            vec![4.0, 12.0, -16.0, 12.0, 37.0, -43.0, -16.0, -43.0, 98.0]
+        );
+        let x = Vector::from_vec(vec![1.0, -2.0, 0.5]);
+        let mut outer = Mat::new((3, 3));
+        for i in 0..3
+        {
+            for j in 0..3
+            {
+                outer[(i, j)] = x[i] * x[j];
+            }
+        }
+
+        let mut chol = a.clone().cholesky().unwrap();
+        chol.update(&x);
+        let updated = a.clone() + outer.clone();
+        assert!(chol.l.approximately(&updated.clone().cholesky().unwrap().l, 1e-10));
+
+        chol.downdate(&x).unwrap();
+        assert!(chol.l.approximately(&a.clone().cholesky().unwrap().l, 1e-10));
+
+        let big = Vector::from_vec(vec![3.0, 0.0, 0.0]);
+        assert!(chol.downdate(&big).is_err());
+        assert!(chol.l.approximately(&a.cholesky().unwrap().l, 1e-10));
+    }
+}